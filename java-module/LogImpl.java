@@ -1,14 +0,0 @@
-package wit_trinity_module;
-
-import java.nio.charset.StandardCharsets;
-import java.util.ArrayList;
-
-import org.teavm.interop.Memory;
-import org.teavm.interop.Address;
-import org.teavm.interop.Import;
-import org.teavm.interop.Export;
-
-public class LogImpl {
-    
-}
-
@@ -1,10 +1,13 @@
 //! High-level library providing a trait that, once implemented, hides the complexity of
 //! Wit bindings.
 
-use std::collections::HashMap;
+use serde::de::DeserializeOwned;
 use trinity_module::exports::trinity::module;
 
 pub mod trinity_module;
+pub use module::messaging::{
+    Capability, InboundMessageV1 as InboundMessage, Message, MessageFormat,
+};
 pub use trinity_module::export;
 
 /// Implements a command for a given type, assuming the type implements the `TrinityCommand` trait.
@@ -15,16 +18,23 @@ macro_rules! impl_command {
     };
 }
 
+/// Creates a [`Manifest`] using the name and version of the current crate.
+#[macro_export]
+macro_rules! manifest {
+    () => {
+        $crate::Manifest::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+    };
+}
+
 fn consume_client(client: CommandClient) -> Vec<module::messaging::Action> {
     let mut actions = Vec::new();
 
-    actions.extend(client.messages.into_iter().map(|msg| {
-        module::messaging::Action::Respond(module::messaging::Message {
-            text: msg.1,
-            html: None,
-            to: msg.0 .0,
-        })
-    }));
+    actions.extend(
+        client
+            .messages
+            .into_iter()
+            .map(module::messaging::Action::Respond),
+    );
 
     actions.extend(
         client
@@ -33,39 +43,83 @@ fn consume_client(client: CommandClient) -> Vec<module::messaging::Action> {
             .map(|reaction| module::messaging::Action::React(reaction)),
     );
 
+    actions.extend(
+        client
+            .attachments
+            .into_iter()
+            .map(|attachment| module::messaging::Action::Attach(attachment.0)),
+    );
+
     actions
 }
 
+/// Parses the configuration passed by the host as JSON.
+fn module_config(config: Option<String>) -> Result<ModuleConfig, String> {
+    let config = config
+        .map(|config| serde_json::from_str(&config))
+        .transpose()
+        .map_err(|err| format!("malformed config: {err}"))?;
+    Ok(ModuleConfig(config))
+}
+
 // Implement `TrinityCommand`, and get an implementation of `Guest` for free!
 impl<T: TrinityCommand> module::messaging::Guest for T {
-    fn init(config: Option<Vec<(String, String)>>) {
-        // Convert the Vec of tuples to a HashMap for convenience.
-        let config = match config {
-            Some(cfg) => cfg
-                .iter()
-                .map(|(k, v)| (k.to_string(), v.to_string()))
-                .collect(),
-            None => HashMap::new(),
+    fn manifest(config: Option<String>) -> module::messaging::ModuleManifest {
+        // A malformed config is reported by `init`.
+        let config = module_config(config).unwrap_or(ModuleConfig(None));
+        Self::manifest(&config).0
+    }
+
+    fn migrations() -> Vec<String> {
+        Self::migrations()
+            .iter()
+            .map(|migration| migration.name.to_owned())
+            .collect()
+    }
+
+    fn migrate(name: String) -> Result<(), String> {
+        let Some(migration) = Self::migrations().into_iter().find(|m| m.name == name) else {
+            return Err(format!("unknown migration {name}"));
         };
+        (migration.up)()
+    }
+
+    fn init(config: Option<String>) -> Result<(), String> {
+        Self::init(module_config(config)?)
+    }
 
-        Self::init(config);
+    fn shutdown() {
+        Self::on_shutdown();
     }
 
     fn help(topic: Option<String>) -> String {
         Self::on_help(topic.as_deref())
     }
 
-    fn on_msg(
-        content: String,
-        author_id: String,
-        _author_name: String,
-        room: String,
-    ) -> Vec<module::messaging::Action> {
-        let mut client = CommandClient::new(room, author_id.clone());
+    fn on_msg(msg: InboundMessage) -> Vec<module::messaging::Action> {
+        let mut client = CommandClient::new(msg.room.clone(), msg.sender_id.clone());
+        let content = msg.content.clone();
+        client.inbound_msg = Some(msg);
         Self::on_msg(&mut client, &content);
         consume_client(client)
     }
 
+    fn on_reply(msg: InboundMessage, in_reply_to: String) -> Vec<module::messaging::Action> {
+        let mut client = CommandClient::new(msg.room.clone(), msg.sender_id.clone());
+        let content = msg.content.clone();
+        client.inbound_msg = Some(msg);
+        Self::on_reply(&mut client, &content, &in_reply_to);
+        consume_client(client)
+    }
+
+    fn on_edit(msg: InboundMessage, original: String) -> Vec<module::messaging::Action> {
+        let mut client = CommandClient::new(msg.room.clone(), msg.sender_id.clone());
+        let content = msg.content.clone();
+        client.inbound_msg = Some(msg);
+        Self::on_edit(&mut client, &content, &original);
+        consume_client(client)
+    }
+
     fn admin(cmd: String, author_id: String, room: String) -> Vec<module::messaging::Action> {
         let mut client = CommandClient::new(room.clone(), author_id);
         Self::on_admin(&mut client, &cmd);
@@ -73,13 +127,13 @@ impl<T: TrinityCommand> module::messaging::Guest for T {
     }
 }
 
-pub struct Recipient(pub String);
-
 pub struct CommandClient {
     inbound_msg_room: String,
     inbound_msg_author: String,
-    pub messages: Vec<(Recipient, String)>,
+    inbound_msg: Option<InboundMessage>,
+    pub messages: Vec<Message>,
     pub reactions: Vec<String>,
+    pub attachments: Vec<Attachment>,
 }
 
 impl CommandClient {
@@ -87,8 +141,10 @@ impl CommandClient {
         Self {
             inbound_msg_room: room,
             inbound_msg_author: author,
+            inbound_msg: None,
             messages: Default::default(),
             reactions: Default::default(),
+            attachments: Default::default(),
         }
     }
 
@@ -102,6 +158,14 @@ impl CommandClient {
         &self.inbound_msg_room
     }
 
+    /// The message being handled, with its metadata (event ID, thread, reply, etc.).
+    ///
+    /// This is set when handling a message in `on_msg`, a reply in `on_reply` or an edit in
+    /// `on_edit`, not for admin commands.
+    pub fn message(&self) -> Option<&InboundMessage> {
+        self.inbound_msg.as_ref()
+    }
+
     /// Queues a message to be sent to the author of the original message.
     pub fn respond(&mut self, msg: impl Into<String>) {
         self.respond_to(msg.into(), self.inbound_msg_author.clone())
@@ -109,7 +173,58 @@ impl CommandClient {
 
     /// Queues a message to be sent to someone else.
     pub fn respond_to(&mut self, msg: String, author: String) {
-        self.messages.push((Recipient(author), msg));
+        self.push_message(author, msg, MessageFormat::Plain, Vec::new());
+    }
+
+    /// Queues a Markdown message to be sent to the author of the original message.
+    ///
+    /// The host renders it to HTML; clients that can't display HTML show the Markdown source.
+    pub fn respond_markdown(&mut self, msg: impl Into<String>) {
+        let author = self.inbound_msg_author.clone();
+        self.push_message(author, msg.into(), MessageFormat::Markdown, Vec::new());
+    }
+
+    /// Queues an HTML message to be sent to the author of the original message.
+    ///
+    /// `text` is the plain-text fallback for clients that can't display HTML. The host strips
+    /// any tag or attribute that's not allowed in Matrix messages.
+    pub fn respond_html(&mut self, text: impl Into<String>, html: impl Into<String>) {
+        let author = self.inbound_msg_author.clone();
+        self.push_message(
+            author,
+            text.into(),
+            MessageFormat::Html(html.into()),
+            Vec::new(),
+        );
+    }
+
+    /// Queues a message addressed to the given user, who gets notified about it.
+    ///
+    /// The host replaces the user ID with a link to the user, showing their display name.
+    pub fn respond_mentioning(&mut self, user: &str, msg: impl AsRef<str>) {
+        let author = self.inbound_msg_author.clone();
+        self.push_message(
+            author,
+            format!("{user}: {}", msg.as_ref()),
+            MessageFormat::Plain,
+            vec![user.to_owned()],
+        );
+    }
+
+    fn push_message(
+        &mut self,
+        to: String,
+        text: String,
+        format: MessageFormat,
+        mentions: Vec<String>,
+    ) {
+        self.messages.push(Message {
+            text,
+            format,
+            to,
+            mentions,
+            mention_room: false,
+        });
     }
 
     pub fn react_with(&mut self, reaction: String) {
@@ -119,28 +234,219 @@ impl CommandClient {
     pub fn react_with_ok(&mut self) {
         self.react_with("👌".to_owned());
     }
+
+    /// Queues an image to be uploaded and sent in the room.
+    ///
+    /// Attachments whose mimetype isn't an image are sent as files.
+    pub fn send_image(&mut self, image: Attachment) {
+        self.attachments.push(image);
+    }
+}
+
+/// A file to be uploaded and sent in the room, see [`CommandClient::send_image`].
+pub struct Attachment(module::messaging::Attachment);
+
+impl Attachment {
+    pub fn new(filename: &str, mimetype: &str, data: Vec<u8>) -> Self {
+        Self(module::messaging::Attachment {
+            filename: filename.to_owned(),
+            mimetype: mimetype.to_owned(),
+            data,
+            width: None,
+            height: None,
+            thumbnail: None,
+        })
+    }
+
+    /// Sets the dimensions of the image, in pixels.
+    pub fn dimensions(mut self, width: u32, height: u32) -> Self {
+        self.0.width = Some(width);
+        self.0.height = Some(height);
+        self
+    }
+
+    /// Sets a smaller version of the image, displayed by clients before the full image is
+    /// downloaded.
+    pub fn thumbnail(mut self, data: Vec<u8>, mimetype: &str, width: u32, height: u32) -> Self {
+        self.0.thumbnail = Some(module::messaging::Thumbnail {
+            data,
+            mimetype: mimetype.to_owned(),
+            width,
+            height,
+        });
+        self
+    }
+}
+
+/// Static description of a command, sent to the host when the command is loaded.
+pub struct Manifest(module::messaging::ModuleManifest);
+
+impl Manifest {
+    /// Creates a new manifest with the given display name and version.
+    ///
+    /// See also the [`manifest!`] macro, which uses the name and version of the current crate.
+    pub fn new(name: &str, version: &str) -> Self {
+        Self(module::messaging::ModuleManifest {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            author: None,
+            commands: Vec::new(),
+            triggers: Vec::new(),
+            capabilities: Vec::new(),
+            config_schema: Vec::new(),
+        })
+    }
+
+    pub fn author(mut self, author: &str) -> Self {
+        self.0.author = Some(author.to_owned());
+        self
+    }
+
+    /// Declares a command handled by this module, e.g. `!toot`.
+    ///
+    /// Messages starting with the command will be sent to `on_msg`.
+    pub fn command(mut self, command: &str) -> Self {
+        self.0.commands.push(command.to_owned());
+        self.0
+            .triggers
+            .push(module::messaging::Trigger::Prefix(command.to_owned()));
+        self
+    }
+
+    /// Messages matching the given regular expression will be sent to `on_msg`.
+    pub fn trigger_regex(mut self, regex: &str) -> Self {
+        self.0
+            .triggers
+            .push(module::messaging::Trigger::Regex(regex.to_owned()));
+        self
+    }
+
+    /// All the messages will be sent to `on_msg`.
+    ///
+    /// Use sparingly, as this means the command will be called for every message, in every room.
+    pub fn catch_all(mut self) -> Self {
+        self.0.triggers.push(module::messaging::Trigger::All);
+        self
+    }
+
+    /// Declares a host interface this module needs.
+    ///
+    /// The host refuses to load a module using an interface it didn't declare.
+    pub fn capability(mut self, capability: Capability) -> Self {
+        self.0.capabilities.push(capability);
+        self
+    }
+
+    /// Declares a configuration key this module understands.
+    ///
+    /// The host refuses to load a module if a required key is missing from its configuration.
+    pub fn config_key(mut self, name: &str, description: &str, required: bool) -> Self {
+        self.0.config_schema.push(module::messaging::ConfigKey {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            required,
+        });
+        self
+    }
+}
+
+/// Configuration of a command, as set in the `modules_config` section of the bot's config.
+pub struct ModuleConfig(Option<serde_json::Value>);
+
+impl ModuleConfig {
+    /// Deserializes the configuration into a typed value.
+    ///
+    /// A missing configuration is deserialized as an empty table, so that a struct whose fields
+    /// all have defaults can be used for optional configuration.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, String> {
+        let value = self
+            .0
+            .clone()
+            .unwrap_or_else(|| serde_json::Value::Object(Default::default()));
+        serde_json::from_value(value).map_err(|err| format!("invalid config: {err}"))
+    }
+
+    /// Raw configuration, if the command is configured.
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        self.0.as_ref()
+    }
+}
+
+/// A data migration, used to rewrite values from the key-value store whose format changed.
+pub struct Migration {
+    /// Unique name of the migration.
+    pub name: &'static str,
+    /// Code running the migration; running it again on already migrated data must be harmless.
+    pub up: fn() -> Result<(), String>,
 }
 
 pub trait TrinityCommand {
+    /// Describes the command: name, version, handled commands, needed capabilities, etc.
+    ///
+    /// The configuration is the same as the one given to `init`, so that e.g. the triggers can
+    /// depend on it. The [`manifest!`] macro is a good starting point to implement this.
+    fn manifest(config: &ModuleConfig) -> Manifest;
+
     /// Code that will be called once during initialization of the command. This is a good time to
     /// retrieve settings from the database and cache them locally, if needs be, or run any
     /// initialization code that shouldn't run on every message later.
-    fn init(_config: HashMap<String, String>) {}
+    ///
+    /// Returning an error (e.g. because of an invalid configuration) prevents the command from
+    /// being loaded.
+    fn init(_config: ModuleConfig) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Code that will be called right before the command is unloaded, replaced by a new version,
+    /// or when the bot exits. This is a good time to flush any in-memory state to the database.
+    ///
+    /// If the new version fails to initialize, `init` is called again, and the command keeps
+    /// running.
+    fn on_shutdown() {}
+
+    /// Data migrations for this command, in order of application.
+    ///
+    /// Each migration runs before `init`, and usually only once; however it runs again if the bot
+    /// stops before having recorded its completion, so it must be idempotent. Once a migration
+    /// has been published, it must never be removed nor reordered; new migrations go at the end
+    /// of the list.
+    fn migrations() -> Vec<Migration> {
+        Vec::new()
+    }
 
     /// Handle a message received in a room where the bot is present.
     ///
     /// The message isn't identified as a request for help or an admin command. Those are handled
-    /// respectively by `on_help` and `on_admin`.
+    /// respectively by `on_help` and `on_admin`. Only the messages matching one of the triggers
+    /// declared in the manifest are received here.
     ///
     /// This should always be implemented, otherwise the command doesn't do anything.
     fn on_msg(client: &mut CommandClient, content: &str);
 
+    /// Handle a reply to a message this module sent, or a message in a thread started by one.
+    ///
+    /// `in_reply_to` is the event ID of the module's message. Such messages are received here
+    /// first, regardless of the triggers; if no response is queued, they're then dispatched as
+    /// usual to `on_msg`. By default, nothing happens here.
+    fn on_reply(_client: &mut CommandClient, _content: &str, _in_reply_to: &str) {}
+
+    /// Handle an edit of a message this module responded to.
+    ///
+    /// `content` is the new content of the message, and `original` the event ID of the edited
+    /// message. Responses queued here replace the ones sent for the original message, in order.
+    /// By default, edits are ignored.
+    fn on_edit(_client: &mut CommandClient, _content: &str, _original: &str) {}
+
     /// Respond to a help request, for this specific command.
     ///
     /// If the topic is not set, then this should return a general description of the command, with
     /// hints to the possible topics. If the topic is set, then this function should document
     /// something related to the specific topic.
     ///
+    /// The help is rendered as Markdown. Topics can be nested, in which case they're separated by
+    /// a single space (e.g. `admin allow`); returning an empty string makes the host fall back to
+    /// the parent topic.
+    ///
     /// This should always be implemented, at least to document what's the command's purpose.
     fn on_help(_topic: Option<&str>) -> String;
 
@@ -13,11 +13,44 @@ pub mod exports {
                 #[doc(hidden)]
                 static __FORCE_SECTION_REF: fn() = super::super::super::super::__link_custom_section_describing_imports;
                 use super::super::super::super::_rt;
+                /// How the text of a message should be rendered.
+                #[derive(Clone)]
+                pub enum MessageFormat {
+                    /// Plain text, sent as is.
+                    Plain,
+                    /// CommonMark, rendered to HTML by the host.
+                    Markdown,
+                    /// The given HTML, sanitized by the host; the message's text is the plain-text fallback.
+                    Html(_rt::String),
+                }
+                impl ::core::fmt::Debug for MessageFormat {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            MessageFormat::Plain => {
+                                f.debug_tuple("MessageFormat::Plain").finish()
+                            }
+                            MessageFormat::Markdown => {
+                                f.debug_tuple("MessageFormat::Markdown").finish()
+                            }
+                            MessageFormat::Html(e) => {
+                                f.debug_tuple("MessageFormat::Html").field(e).finish()
+                            }
+                        }
+                    }
+                }
                 #[derive(Clone)]
                 pub struct Message {
                     pub text: _rt::String,
-                    pub html: Option<_rt::String>,
+                    pub format: MessageFormat,
                     pub to: _rt::String,
+                    /// Users mentioned in the message; occurrences of their IDs in the text are turned into
+                    /// links to them.
+                    pub mentions: _rt::Vec<_rt::String>,
+                    /// Whether the message mentions the whole room.
+                    pub mention_room: bool,
                 }
                 impl ::core::fmt::Debug for Message {
                     fn fmt(
@@ -26,16 +59,69 @@ pub mod exports {
                     ) -> ::core::fmt::Result {
                         f.debug_struct("Message")
                             .field("text", &self.text)
-                            .field("html", &self.html)
+                            .field("format", &self.format)
                             .field("to", &self.to)
+                            .field("mentions", &self.mentions)
+                            .field("mention-room", &self.mention_room)
                             .finish()
                     }
                 }
                 pub type Reaction = _rt::String;
+                /// A smaller version of an image attachment.
+                #[derive(Clone)]
+                pub struct Thumbnail {
+                    pub data: _rt::Vec<u8>,
+                    pub mimetype: _rt::String,
+                    pub width: u32,
+                    pub height: u32,
+                }
+                impl ::core::fmt::Debug for Thumbnail {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("Thumbnail")
+                            .field("data", &self.data)
+                            .field("mimetype", &self.mimetype)
+                            .field("width", &self.width)
+                            .field("height", &self.height)
+                            .finish()
+                    }
+                }
+                /// A file to upload to the homeserver and send in the room.
+                ///
+                /// Images (per their mimetype) are sent as `m.image` events, other files as `m.file`.
+                #[derive(Clone)]
+                pub struct Attachment {
+                    pub filename: _rt::String,
+                    pub mimetype: _rt::String,
+                    pub data: _rt::Vec<u8>,
+                    /// Width of the image, in pixels.
+                    pub width: Option<u32>,
+                    /// Height of the image, in pixels.
+                    pub height: Option<u32>,
+                    pub thumbnail: Option<Thumbnail>,
+                }
+                impl ::core::fmt::Debug for Attachment {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("Attachment")
+                            .field("filename", &self.filename)
+                            .field("mimetype", &self.mimetype)
+                            .field("data", &self.data)
+                            .field("width", &self.width)
+                            .field("height", &self.height)
+                            .field("thumbnail", &self.thumbnail)
+                            .finish()
+                    }
+                }
                 #[derive(Clone)]
                 pub enum Action {
                     Respond(Message),
                     React(Reaction),
+                    Attach(Attachment),
                 }
                 impl ::core::fmt::Debug for Action {
                     fn fmt(
@@ -49,77 +135,202 @@ pub mod exports {
                             Action::React(e) => {
                                 f.debug_tuple("Action::React").field(e).finish()
                             }
+                            Action::Attach(e) => {
+                                f.debug_tuple("Action::Attach").field(e).finish()
+                            }
                         }
                     }
                 }
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn _export_init_cabi<T: Guest>(
-                    arg0: i32,
-                    arg1: *mut u8,
-                    arg2: usize,
-                ) {
-                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    T::init(
-                        match arg0 {
-                            0 => None,
-                            1 => {
-                                let e = {
-                                    let base6 = arg1;
-                                    let len6 = arg2;
-                                    let mut result6 = _rt::Vec::with_capacity(len6);
-                                    for i in 0..len6 {
-                                        let base = base6
-                                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
-                                        let e6 = {
-                                            let l0 = *base.add(0).cast::<*mut u8>();
-                                            let l1 = *base
-                                                .add(::core::mem::size_of::<*const u8>())
-                                                .cast::<usize>();
-                                            let len2 = l1;
-                                            let bytes2 = _rt::Vec::from_raw_parts(
-                                                l0.cast(),
-                                                len2,
-                                                len2,
-                                            );
-                                            let l3 = *base
-                                                .add(2 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<*mut u8>();
-                                            let l4 = *base
-                                                .add(3 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<usize>();
-                                            let len5 = l4;
-                                            let bytes5 = _rt::Vec::from_raw_parts(
-                                                l3.cast(),
-                                                len5,
-                                                len5,
-                                            );
-                                            (_rt::string_lift(bytes2), _rt::string_lift(bytes5))
-                                        };
-                                        result6.push(e6);
-                                    }
-                                    _rt::cabi_dealloc(
-                                        base6,
-                                        len6 * (4 * ::core::mem::size_of::<*const u8>()),
-                                        ::core::mem::size_of::<*const u8>(),
-                                    );
-                                    result6
-                                };
-                                Some(e)
+                /// A host interface a module can import.
+                #[repr(u8)]
+                #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+                pub enum Capability {
+                    Kv,
+                    Log,
+                    SyncRequest,
+                    Sys,
+                    History,
+                    RoomInfo,
+                    RoomState,
+                    Typing,
+                }
+                impl ::core::fmt::Debug for Capability {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            Capability::Kv => f.debug_tuple("Capability::Kv").finish(),
+                            Capability::Log => f.debug_tuple("Capability::Log").finish(),
+                            Capability::SyncRequest => {
+                                f.debug_tuple("Capability::SyncRequest").finish()
                             }
-                            _ => _rt::invalid_enum_discriminant(),
-                        },
-                    );
+                            Capability::Sys => f.debug_tuple("Capability::Sys").finish(),
+                            Capability::History => {
+                                f.debug_tuple("Capability::History").finish()
+                            }
+                            Capability::RoomInfo => {
+                                f.debug_tuple("Capability::RoomInfo").finish()
+                            }
+                            Capability::RoomState => {
+                                f.debug_tuple("Capability::RoomState").finish()
+                            }
+                            Capability::Typing => {
+                                f.debug_tuple("Capability::Typing").finish()
+                            }
+                        }
+                    }
+                }
+                impl Capability {
+                    #[doc(hidden)]
+                    pub unsafe fn _lift(val: u8) -> Capability {
+                        if !cfg!(debug_assertions) {
+                            return ::core::mem::transmute(val);
+                        }
+                        match val {
+                            0 => Capability::Kv,
+                            1 => Capability::Log,
+                            2 => Capability::SyncRequest,
+                            3 => Capability::Sys,
+                            4 => Capability::History,
+                            5 => Capability::RoomInfo,
+                            6 => Capability::RoomState,
+                            7 => Capability::Typing,
+                            _ => panic!("invalid enum discriminant"),
+                        }
+                    }
+                }
+                /// Describes which messages a module wants to handle.
+                #[derive(Clone)]
+                pub enum Trigger {
+                    /// Messages starting with the given prefix, e.g. `!toot`.
+                    Prefix(_rt::String),
+                    /// Messages matching the given regular expression.
+                    Regex(_rt::String),
+                    /// All the messages.
+                    All,
+                }
+                impl ::core::fmt::Debug for Trigger {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            Trigger::Prefix(e) => {
+                                f.debug_tuple("Trigger::Prefix").field(e).finish()
+                            }
+                            Trigger::Regex(e) => {
+                                f.debug_tuple("Trigger::Regex").field(e).finish()
+                            }
+                            Trigger::All => f.debug_tuple("Trigger::All").finish(),
+                        }
+                    }
+                }
+                /// A message received in a room.
+                ///
+                /// This record is versioned: it's never changed in place, a new version with a different
+                /// name is introduced instead.
+                #[derive(Clone)]
+                pub struct InboundMessageV1 {
+                    /// Text of the message.
+                    pub content: _rt::String,
+                    pub event_id: _rt::String,
+                    /// When the message was sent, in milliseconds since the unix epoch.
+                    pub timestamp: u64,
+                    pub sender_id: _rt::String,
+                    /// Display name of the sender in the room, if any.
+                    pub sender_name: Option<_rt::String>,
+                    pub room: _rt::String,
+                    /// Whether the room is a direct conversation with a single user.
+                    pub is_direct: bool,
+                    /// Root of the thread the message was sent in, if any.
+                    pub thread_root: Option<_rt::String>,
+                    /// The message this one is a reply to, if any.
+                    pub reply_to: Option<_rt::String>,
+                    /// If the message is an edit, the message it replaces.
+                    pub edit_of: Option<_rt::String>,
+                }
+                impl ::core::fmt::Debug for InboundMessageV1 {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("InboundMessageV1")
+                            .field("content", &self.content)
+                            .field("event-id", &self.event_id)
+                            .field("timestamp", &self.timestamp)
+                            .field("sender-id", &self.sender_id)
+                            .field("sender-name", &self.sender_name)
+                            .field("room", &self.room)
+                            .field("is-direct", &self.is_direct)
+                            .field("thread-root", &self.thread_root)
+                            .field("reply-to", &self.reply_to)
+                            .field("edit-of", &self.edit_of)
+                            .finish()
+                    }
+                }
+                /// A configuration key a module understands.
+                #[derive(Clone)]
+                pub struct ConfigKey {
+                    pub name: _rt::String,
+                    pub description: _rt::String,
+                    pub required: bool,
+                }
+                impl ::core::fmt::Debug for ConfigKey {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("ConfigKey")
+                            .field("name", &self.name)
+                            .field("description", &self.description)
+                            .field("required", &self.required)
+                            .finish()
+                    }
+                }
+                /// Static description of a module.
+                #[derive(Clone)]
+                pub struct ModuleManifest {
+                    /// Display name.
+                    pub name: _rt::String,
+                    pub version: _rt::String,
+                    pub author: Option<_rt::String>,
+                    /// Commands handled by the module, e.g. `!toot`.
+                    pub commands: _rt::Vec<_rt::String>,
+                    /// Messages the module wants to handle; `on-msg` is only called for messages matching
+                    /// at least one of these.
+                    pub triggers: _rt::Vec<Trigger>,
+                    /// Host interfaces the module needs; importing any other one prevents the module from
+                    /// loading.
+                    pub capabilities: _rt::Vec<Capability>,
+                    /// Configuration keys the module understands.
+                    pub config_schema: _rt::Vec<ConfigKey>,
+                }
+                impl ::core::fmt::Debug for ModuleManifest {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("ModuleManifest")
+                            .field("name", &self.name)
+                            .field("version", &self.version)
+                            .field("author", &self.author)
+                            .field("commands", &self.commands)
+                            .field("triggers", &self.triggers)
+                            .field("capabilities", &self.capabilities)
+                            .field("config-schema", &self.config_schema)
+                            .finish()
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_help_cabi<T: Guest>(
+                pub unsafe fn _export_manifest_cabi<T: Guest>(
                     arg0: i32,
                     arg1: *mut u8,
                     arg2: usize,
                 ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result1 = T::help(
+                    let result1 = T::manifest(
                         match arg0 {
                             0 => None,
                             1 => {
@@ -138,114 +349,106 @@ pub mod exports {
                         },
                     );
                     let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec3 = (result1.into_bytes()).into_boxed_slice();
-                    let ptr3 = vec3.as_ptr().cast::<u8>();
-                    let len3 = vec3.len();
-                    ::core::mem::forget(vec3);
-                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
-                    *ptr2.add(0).cast::<*mut u8>() = ptr3.cast_mut();
-                    ptr2
-                }
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn __post_return_help<T: Guest>(arg0: *mut u8) {
-                    let l0 = *arg0.add(0).cast::<*mut u8>();
-                    let l1 = *arg0
-                        .add(::core::mem::size_of::<*const u8>())
-                        .cast::<usize>();
-                    _rt::cabi_dealloc(l0, l1, 1);
-                }
-                #[doc(hidden)]
-                #[allow(non_snake_case)]
-                pub unsafe fn _export_admin_cabi<T: Guest>(
-                    arg0: *mut u8,
-                    arg1: usize,
-                    arg2: *mut u8,
-                    arg3: usize,
-                    arg4: *mut u8,
-                    arg5: usize,
-                ) -> *mut u8 {
-                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let len1 = arg3;
-                    let bytes1 = _rt::Vec::from_raw_parts(arg2.cast(), len1, len1);
-                    let len2 = arg5;
-                    let bytes2 = _rt::Vec::from_raw_parts(arg4.cast(), len2, len2);
-                    let result3 = T::admin(
-                        _rt::string_lift(bytes0),
-                        _rt::string_lift(bytes1),
-                        _rt::string_lift(bytes2),
+                    let ModuleManifest {
+                        name: name3,
+                        version: version3,
+                        author: author3,
+                        commands: commands3,
+                        triggers: triggers3,
+                        capabilities: capabilities3,
+                        config_schema: config_schema3,
+                    } = result1;
+                    let vec4 = (name3.into_bytes()).into_boxed_slice();
+                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                    let len4 = vec4.len();
+                    ::core::mem::forget(vec4);
+                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+                    *ptr2.add(0).cast::<*mut u8>() = ptr4.cast_mut();
+                    let vec5 = (version3.into_bytes()).into_boxed_slice();
+                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                    let len5 = vec5.len();
+                    ::core::mem::forget(vec5);
+                    *ptr2.add(3 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+                    *ptr2
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr5.cast_mut();
+                    match author3 {
+                        Some(e) => {
+                            *ptr2
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (1i32) as u8;
+                            let vec6 = (e.into_bytes()).into_boxed_slice();
+                            let ptr6 = vec6.as_ptr().cast::<u8>();
+                            let len6 = vec6.len();
+                            ::core::mem::forget(vec6);
+                            *ptr2
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len6;
+                            *ptr2
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr6.cast_mut();
+                        }
+                        None => {
+                            *ptr2
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (0i32) as u8;
+                        }
+                    };
+                    let vec8 = commands3;
+                    let len8 = vec8.len();
+                    let layout8 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec8.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
                     );
-                    let ptr4 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec10 = result3;
-                    let len10 = vec10.len();
-                    let layout10 = _rt::alloc::Layout::from_size_align_unchecked(
-                        vec10.len() * (8 * ::core::mem::size_of::<*const u8>()),
+                    let result8 = if layout8.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout8);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec8.into_iter().enumerate() {
+                        let base = result8
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let vec7 = (e.into_bytes()).into_boxed_slice();
+                            let ptr7 = vec7.as_ptr().cast::<u8>();
+                            let len7 = vec7.len();
+                            ::core::mem::forget(vec7);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len7;
+                            *base.add(0).cast::<*mut u8>() = ptr7.cast_mut();
+                        }
+                    }
+                    *ptr2.add(8 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+                    *ptr2
+                        .add(7 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result8;
+                    let vec11 = triggers3;
+                    let len11 = vec11.len();
+                    let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec11.len() * (3 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
-                    let result10 = if layout10.size() != 0 {
-                        let ptr = _rt::alloc::alloc(layout10).cast::<u8>();
+                    let result11 = if layout11.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
                         if ptr.is_null() {
-                            _rt::alloc::handle_alloc_error(layout10);
+                            _rt::alloc::handle_alloc_error(layout11);
                         }
                         ptr
                     } else {
                         ::core::ptr::null_mut()
                     };
-                    for (i, e) in vec10.into_iter().enumerate() {
-                        let base = result10
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    for (i, e) in vec11.into_iter().enumerate() {
+                        let base = result11
+                            .add(i * (3 * ::core::mem::size_of::<*const u8>()));
                         {
                             match e {
-                                Action::Respond(e) => {
+                                Trigger::Prefix(e) => {
                                     *base.add(0).cast::<u8>() = (0i32) as u8;
-                                    let Message { text: text5, html: html5, to: to5 } = e;
-                                    let vec6 = (text5.into_bytes()).into_boxed_slice();
-                                    let ptr6 = vec6.as_ptr().cast::<u8>();
-                                    let len6 = vec6.len();
-                                    ::core::mem::forget(vec6);
-                                    *base
-                                        .add(2 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len6;
-                                    *base
-                                        .add(::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr6.cast_mut();
-                                    match html5 {
-                                        Some(e) => {
-                                            *base
-                                                .add(3 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<u8>() = (1i32) as u8;
-                                            let vec7 = (e.into_bytes()).into_boxed_slice();
-                                            let ptr7 = vec7.as_ptr().cast::<u8>();
-                                            let len7 = vec7.len();
-                                            ::core::mem::forget(vec7);
-                                            *base
-                                                .add(5 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<usize>() = len7;
-                                            *base
-                                                .add(4 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<*mut u8>() = ptr7.cast_mut();
-                                        }
-                                        None => {
-                                            *base
-                                                .add(3 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<u8>() = (0i32) as u8;
-                                        }
-                                    };
-                                    let vec8 = (to5.into_bytes()).into_boxed_slice();
-                                    let ptr8 = vec8.as_ptr().cast::<u8>();
-                                    let len8 = vec8.len();
-                                    ::core::mem::forget(vec8);
-                                    *base
-                                        .add(7 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len8;
-                                    *base
-                                        .add(6 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr8.cast_mut();
-                                }
-                                Action::React(e) => {
-                                    *base.add(0).cast::<u8>() = (1i32) as u8;
                                     let vec9 = (e.into_bytes()).into_boxed_slice();
                                     let ptr9 = vec9.as_ptr().cast::<u8>();
                                     let len9 = vec9.len();
@@ -257,25 +460,1896 @@ pub mod exports {
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>() = ptr9.cast_mut();
                                 }
+                                Trigger::Regex(e) => {
+                                    *base.add(0).cast::<u8>() = (1i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                Trigger::All => {
+                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                }
+                            }
+                        }
+                    }
+                    *ptr2
+                        .add(10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>() = len11;
+                    *ptr2
+                        .add(9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result11;
+                    let vec12 = capabilities3;
+                    let len12 = vec12.len();
+                    let layout12 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec12.len() * 1,
+                        1,
+                    );
+                    let result12 = if layout12.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout12).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout12);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec12.into_iter().enumerate() {
+                        let base = result12.add(i * 1);
+                        {
+                            *base.add(0).cast::<u8>() = (e.clone() as i32) as u8;
+                        }
+                    }
+                    *ptr2
+                        .add(12 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>() = len12;
+                    *ptr2
+                        .add(11 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result12;
+                    let vec16 = config_schema3;
+                    let len16 = vec16.len();
+                    let layout16 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec16.len() * (5 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result16 = if layout16.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout16).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout16);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec16.into_iter().enumerate() {
+                        let base = result16
+                            .add(i * (5 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let ConfigKey {
+                                name: name13,
+                                description: description13,
+                                required: required13,
+                            } = e;
+                            let vec14 = (name13.into_bytes()).into_boxed_slice();
+                            let ptr14 = vec14.as_ptr().cast::<u8>();
+                            let len14 = vec14.len();
+                            ::core::mem::forget(vec14);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len14;
+                            *base.add(0).cast::<*mut u8>() = ptr14.cast_mut();
+                            let vec15 = (description13.into_bytes()).into_boxed_slice();
+                            let ptr15 = vec15.as_ptr().cast::<u8>();
+                            let len15 = vec15.len();
+                            ::core::mem::forget(vec15);
+                            *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len15;
+                            *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr15.cast_mut();
+                            *base
+                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                .cast::<u8>() = (match required13 {
+                                true => 1,
+                                false => 0,
+                            }) as u8;
+                        }
+                    }
+                    *ptr2
+                        .add(14 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>() = len16;
+                    *ptr2
+                        .add(13 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result16;
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_manifest<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l0, l1, 1);
+                    let l2 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l3 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l2, l3, 1);
+                    let l4 = i32::from(
+                        *arg0.add(4 * ::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                    );
+                    match l4 {
+                        0 => {}
+                        _ => {
+                            let l5 = *arg0
+                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(6 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l5, l6, 1);
+                        }
+                    }
+                    let l7 = *arg0
+                        .add(7 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l8 = *arg0
+                        .add(8 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base11 = l7;
+                    let len11 = l8;
+                    for i in 0..len11 {
+                        let base = base11
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l9 = *base.add(0).cast::<*mut u8>();
+                            let l10 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l9, l10, 1);
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base11,
+                        len11 * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let l12 = *arg0
+                        .add(9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l13 = *arg0
+                        .add(10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base19 = l12;
+                    let len19 = l13;
+                    for i in 0..len19 {
+                        let base = base19
+                            .add(i * (3 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l14 = i32::from(*base.add(0).cast::<u8>());
+                            match l14 {
+                                0 => {
+                                    let l15 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
+                                }
+                                1 => {
+                                    let l17 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base19,
+                        len19 * (3 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let l20 = *arg0
+                        .add(11 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l21 = *arg0
+                        .add(12 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base22 = l20;
+                    let len22 = l21;
+                    _rt::cabi_dealloc(base22, len22 * 1, 1);
+                    let l23 = *arg0
+                        .add(13 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l24 = *arg0
+                        .add(14 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base29 = l23;
+                    let len29 = l24;
+                    for i in 0..len29 {
+                        let base = base29
+                            .add(i * (5 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l25 = *base.add(0).cast::<*mut u8>();
+                            let l26 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l25, l26, 1);
+                            let l27 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l28 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l27, l28, 1);
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base29,
+                        len29 * (5 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_migrations_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::migrations();
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec3 = result0;
+                    let len3 = vec3.len();
+                    let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec3.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result3 = if layout3.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout3);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec3.into_iter().enumerate() {
+                        let base = result3
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let vec2 = (e.into_bytes()).into_boxed_slice();
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            ::core::mem::forget(vec2);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len2;
+                            *base.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                        }
+                    }
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                    *ptr1.add(0).cast::<*mut u8>() = result3;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_migrations<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base4 = l0;
+                    let len4 = l1;
+                    for i in 0..len4 {
+                        let base = base4
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l2 = *base.add(0).cast::<*mut u8>();
+                            let l3 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l2, l3, 1);
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base4,
+                        len4 * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_migrate_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let result1 = T::migrate(_rt::string_lift(bytes0));
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(_) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_migrate<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_init_cabi<T: Guest>(
+                    arg0: i32,
+                    arg1: *mut u8,
+                    arg2: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result1 = T::init(
+                        match arg0 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let len0 = arg2;
+                                    let bytes0 = _rt::Vec::from_raw_parts(
+                                        arg1.cast(),
+                                        len0,
+                                        len0,
+                                    );
+                                    _rt::string_lift(bytes0)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(_) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_init<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_shutdown_cabi<T: Guest>() {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::shutdown();
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_help_cabi<T: Guest>(
+                    arg0: i32,
+                    arg1: *mut u8,
+                    arg2: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result1 = T::help(
+                        match arg0 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let len0 = arg2;
+                                    let bytes0 = _rt::Vec::from_raw_parts(
+                                        arg1.cast(),
+                                        len0,
+                                        len0,
+                                    );
+                                    _rt::string_lift(bytes0)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec3 = (result1.into_bytes()).into_boxed_slice();
+                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                    let len3 = vec3.len();
+                    ::core::mem::forget(vec3);
+                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                    *ptr2.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_help<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l0, l1, 1);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_admin_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                    arg4: *mut u8,
+                    arg5: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let len1 = arg3;
+                    let bytes1 = _rt::Vec::from_raw_parts(arg2.cast(), len1, len1);
+                    let len2 = arg5;
+                    let bytes2 = _rt::Vec::from_raw_parts(arg4.cast(), len2, len2);
+                    let result3 = T::admin(
+                        _rt::string_lift(bytes0),
+                        _rt::string_lift(bytes1),
+                        _rt::string_lift(bytes2),
+                    );
+                    let ptr4 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec19 = result3;
+                    let len19 = vec19.len();
+                    let layout19 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec19.len() * (24 + 12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result19 = if layout19.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout19).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout19);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec19.into_iter().enumerate() {
+                        let base = result19
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            match e {
+                                Action::Respond(e) => {
+                                    *base.add(0).cast::<u8>() = (0i32) as u8;
+                                    let Message {
+                                        text: text5,
+                                        format: format5,
+                                        to: to5,
+                                        mentions: mentions5,
+                                        mention_room: mention_room5,
+                                    } = e;
+                                    let vec6 = (text5.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                    match format5 {
+                                        MessageFormat::Plain => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                        MessageFormat::Markdown => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                        }
+                                        MessageFormat::Html(e) => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (2i32) as u8;
+                                            let vec7 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr7 = vec7.as_ptr().cast::<u8>();
+                                            let len7 = vec7.len();
+                                            ::core::mem::forget(vec7);
+                                            *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len7;
+                                            *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr7.cast_mut();
+                                        }
+                                    }
+                                    let vec8 = (to5.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                    let vec10 = mentions5;
+                                    let len10 = vec10.len();
+                                    let layout10 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec10.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result10 = if layout10.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout10).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout10);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec10.into_iter().enumerate() {
+                                        let base = result10
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len9;
+                                            *base.add(0).cast::<*mut u8>() = ptr9.cast_mut();
+                                        }
+                                    }
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result10;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (match mention_room5 {
+                                        true => 1,
+                                        false => 0,
+                                    }) as u8;
+                                }
+                                Action::React(e) => {
+                                    *base.add(0).cast::<u8>() = (1i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                Action::Attach(e) => {
+                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                    let Attachment {
+                                        filename: filename12,
+                                        mimetype: mimetype12,
+                                        data: data12,
+                                        width: width12,
+                                        height: height12,
+                                        thumbnail: thumbnail12,
+                                    } = e;
+                                    let vec13 = (filename12.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                    let vec14 = (mimetype12.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                    let vec15 = (data12).into_boxed_slice();
+                                    let ptr15 = vec15.as_ptr().cast::<u8>();
+                                    let len15 = vec15.len();
+                                    ::core::mem::forget(vec15);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len15;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr15.cast_mut();
+                                    match width12 {
+                                        Some(e) => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(4 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match height12 {
+                                        Some(e) => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(12 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match thumbnail12 {
+                                        Some(e) => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Thumbnail {
+                                                data: data16,
+                                                mimetype: mimetype16,
+                                                width: width16,
+                                                height: height16,
+                                            } = e;
+                                            let vec17 = (data16).into_boxed_slice();
+                                            let ptr17 = vec17.as_ptr().cast::<u8>();
+                                            let len17 = vec17.len();
+                                            ::core::mem::forget(vec17);
+                                            *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len17;
+                                            *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr17.cast_mut();
+                                            let vec18 = (mimetype16.into_bytes()).into_boxed_slice();
+                                            let ptr18 = vec18.as_ptr().cast::<u8>();
+                                            let len18 = vec18.len();
+                                            ::core::mem::forget(vec18);
+                                            *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len18;
+                                            *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr18.cast_mut();
+                                            *base
+                                                .add(16 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(width16);
+                                            *base
+                                                .add(20 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(height16);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
+                            }
+                        }
+                    }
+                    *ptr4.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len19;
+                    *ptr4.add(0).cast::<*mut u8>() = result19;
+                    ptr4
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_admin<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base30 = l0;
+                    let len30 = l1;
+                    for i in 0..len30 {
+                        let base = base30
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l2 = i32::from(*base.add(0).cast::<u8>());
+                            match l2 {
+                                0 => {
+                                    let l3 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l3, l4, 1);
+                                    let l5 = i32::from(
+                                        *base
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l5 {
+                                        0 => {}
+                                        1 => {}
+                                        _ => {
+                                            let l6 = *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l7 = *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l6, l7, 1);
+                                        }
+                                    }
+                                    let l8 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                    let l10 = *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base14 = l10;
+                                    let len14 = l11;
+                                    for i in 0..len14 {
+                                        let base = base14
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let l12 = *base.add(0).cast::<*mut u8>();
+                                            let l13 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base14,
+                                        len14 * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                }
+                                1 => {
+                                    let l15 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
+                                }
+                                _ => {
+                                    let l17 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                    let l19 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                    let l21 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base23 = l21;
+                                    let len23 = l22;
+                                    _rt::cabi_dealloc(base23, len23 * 1, 1);
+                                    let l24 = i32::from(
+                                        *base
+                                            .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l24 {
+                                        0 => {}
+                                        _ => {
+                                            let l25 = *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l26 = *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base27 = l25;
+                                            let len27 = l26;
+                                            _rt::cabi_dealloc(base27, len27 * 1, 1);
+                                            let l28 = *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l29 = *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l28, l29, 1);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base30,
+                        len30 * (24 + 12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_on_msg_cabi<T: Guest>(arg0: *mut u8) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len2 = l1;
+                    let bytes2 = _rt::Vec::from_raw_parts(l0.cast(), len2, len2);
+                    let l3 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l4 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len5 = l4;
+                    let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+                    let l6 = *arg0
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<i64>();
+                    let l7 = *arg0
+                        .add(8 + 4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l8 = *arg0
+                        .add(8 + 5 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len9 = l8;
+                    let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                    let l10 = i32::from(
+                        *arg0
+                            .add(8 + 6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l14 = *arg0
+                        .add(8 + 9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l15 = *arg0
+                        .add(8 + 10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len16 = l15;
+                    let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+                    let l17 = i32::from(
+                        *arg0
+                            .add(8 + 11 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l18 = i32::from(
+                        *arg0
+                            .add(8 + 12 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l22 = i32::from(
+                        *arg0
+                            .add(8 + 15 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l26 = i32::from(
+                        *arg0
+                            .add(8 + 18 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let result30 = T::on_msg(InboundMessageV1 {
+                        content: _rt::string_lift(bytes2),
+                        event_id: _rt::string_lift(bytes5),
+                        timestamp: l6 as u64,
+                        sender_id: _rt::string_lift(bytes9),
+                        sender_name: match l10 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l11 = *arg0
+                                        .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l12 = *arg0
+                                        .add(8 + 8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len13 = l12;
+                                    let bytes13 = _rt::Vec::from_raw_parts(
+                                        l11.cast(),
+                                        len13,
+                                        len13,
+                                    );
+                                    _rt::string_lift(bytes13)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                        room: _rt::string_lift(bytes16),
+                        is_direct: _rt::bool_lift(l17 as u8),
+                        thread_root: match l18 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l19 = *arg0
+                                        .add(8 + 13 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *arg0
+                                        .add(8 + 14 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len21 = l20;
+                                    let bytes21 = _rt::Vec::from_raw_parts(
+                                        l19.cast(),
+                                        len21,
+                                        len21,
+                                    );
+                                    _rt::string_lift(bytes21)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                        reply_to: match l22 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l23 = *arg0
+                                        .add(8 + 16 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l24 = *arg0
+                                        .add(8 + 17 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len25 = l24;
+                                    let bytes25 = _rt::Vec::from_raw_parts(
+                                        l23.cast(),
+                                        len25,
+                                        len25,
+                                    );
+                                    _rt::string_lift(bytes25)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                        edit_of: match l26 {
+                            0 => None,
+                            1 => {
+                                let e = {
+                                    let l27 = *arg0
+                                        .add(8 + 19 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l28 = *arg0
+                                        .add(8 + 20 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let len29 = l28;
+                                    let bytes29 = _rt::Vec::from_raw_parts(
+                                        l27.cast(),
+                                        len29,
+                                        len29,
+                                    );
+                                    _rt::string_lift(bytes29)
+                                };
+                                Some(e)
+                            }
+                            _ => _rt::invalid_enum_discriminant(),
+                        },
+                    });
+                    _rt::cabi_dealloc(
+                        arg0,
+                        16 + 20 * ::core::mem::size_of::<*const u8>(),
+                        8,
+                    );
+                    let ptr31 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec46 = result30;
+                    let len46 = vec46.len();
+                    let layout46 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec46.len() * (24 + 12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result46 = if layout46.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout46).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout46);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec46.into_iter().enumerate() {
+                        let base = result46
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            match e {
+                                Action::Respond(e) => {
+                                    *base.add(0).cast::<u8>() = (0i32) as u8;
+                                    let Message {
+                                        text: text32,
+                                        format: format32,
+                                        to: to32,
+                                        mentions: mentions32,
+                                        mention_room: mention_room32,
+                                    } = e;
+                                    let vec33 = (text32.into_bytes()).into_boxed_slice();
+                                    let ptr33 = vec33.as_ptr().cast::<u8>();
+                                    let len33 = vec33.len();
+                                    ::core::mem::forget(vec33);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len33;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr33.cast_mut();
+                                    match format32 {
+                                        MessageFormat::Plain => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                        MessageFormat::Markdown => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                        }
+                                        MessageFormat::Html(e) => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (2i32) as u8;
+                                            let vec34 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr34 = vec34.as_ptr().cast::<u8>();
+                                            let len34 = vec34.len();
+                                            ::core::mem::forget(vec34);
+                                            *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len34;
+                                            *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr34.cast_mut();
+                                        }
+                                    }
+                                    let vec35 = (to32.into_bytes()).into_boxed_slice();
+                                    let ptr35 = vec35.as_ptr().cast::<u8>();
+                                    let len35 = vec35.len();
+                                    ::core::mem::forget(vec35);
+                                    *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len35;
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr35.cast_mut();
+                                    let vec37 = mentions32;
+                                    let len37 = vec37.len();
+                                    let layout37 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec37.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result37 = if layout37.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout37).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout37);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec37.into_iter().enumerate() {
+                                        let base = result37
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let vec36 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr36 = vec36.as_ptr().cast::<u8>();
+                                            let len36 = vec36.len();
+                                            ::core::mem::forget(vec36);
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len36;
+                                            *base.add(0).cast::<*mut u8>() = ptr36.cast_mut();
+                                        }
+                                    }
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len37;
+                                    *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result37;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (match mention_room32 {
+                                        true => 1,
+                                        false => 0,
+                                    }) as u8;
+                                }
+                                Action::React(e) => {
+                                    *base.add(0).cast::<u8>() = (1i32) as u8;
+                                    let vec38 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr38 = vec38.as_ptr().cast::<u8>();
+                                    let len38 = vec38.len();
+                                    ::core::mem::forget(vec38);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len38;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr38.cast_mut();
+                                }
+                                Action::Attach(e) => {
+                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                    let Attachment {
+                                        filename: filename39,
+                                        mimetype: mimetype39,
+                                        data: data39,
+                                        width: width39,
+                                        height: height39,
+                                        thumbnail: thumbnail39,
+                                    } = e;
+                                    let vec40 = (filename39.into_bytes()).into_boxed_slice();
+                                    let ptr40 = vec40.as_ptr().cast::<u8>();
+                                    let len40 = vec40.len();
+                                    ::core::mem::forget(vec40);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len40;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr40.cast_mut();
+                                    let vec41 = (mimetype39.into_bytes()).into_boxed_slice();
+                                    let ptr41 = vec41.as_ptr().cast::<u8>();
+                                    let len41 = vec41.len();
+                                    ::core::mem::forget(vec41);
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len41;
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr41.cast_mut();
+                                    let vec42 = (data39).into_boxed_slice();
+                                    let ptr42 = vec42.as_ptr().cast::<u8>();
+                                    let len42 = vec42.len();
+                                    ::core::mem::forget(vec42);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len42;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr42.cast_mut();
+                                    match width39 {
+                                        Some(e) => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(4 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match height39 {
+                                        Some(e) => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(12 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match thumbnail39 {
+                                        Some(e) => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Thumbnail {
+                                                data: data43,
+                                                mimetype: mimetype43,
+                                                width: width43,
+                                                height: height43,
+                                            } = e;
+                                            let vec44 = (data43).into_boxed_slice();
+                                            let ptr44 = vec44.as_ptr().cast::<u8>();
+                                            let len44 = vec44.len();
+                                            ::core::mem::forget(vec44);
+                                            *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len44;
+                                            *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr44.cast_mut();
+                                            let vec45 = (mimetype43.into_bytes()).into_boxed_slice();
+                                            let ptr45 = vec45.as_ptr().cast::<u8>();
+                                            let len45 = vec45.len();
+                                            ::core::mem::forget(vec45);
+                                            *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len45;
+                                            *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr45.cast_mut();
+                                            *base
+                                                .add(16 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(width43);
+                                            *base
+                                                .add(20 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(height43);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
+                            }
+                        }
+                    }
+                    *ptr31.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len46;
+                    *ptr31.add(0).cast::<*mut u8>() = result46;
+                    ptr31
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_on_msg<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base30 = l0;
+                    let len30 = l1;
+                    for i in 0..len30 {
+                        let base = base30
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l2 = i32::from(*base.add(0).cast::<u8>());
+                            match l2 {
+                                0 => {
+                                    let l3 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l3, l4, 1);
+                                    let l5 = i32::from(
+                                        *base
+                                            .add(3 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l5 {
+                                        0 => {}
+                                        1 => {}
+                                        _ => {
+                                            let l6 = *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l7 = *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l6, l7, 1);
+                                        }
+                                    }
+                                    let l8 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                    let l10 = *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base14 = l10;
+                                    let len14 = l11;
+                                    for i in 0..len14 {
+                                        let base = base14
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let l12 = *base.add(0).cast::<*mut u8>();
+                                            let l13 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base14,
+                                        len14 * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                }
+                                1 => {
+                                    let l15 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
+                                }
+                                _ => {
+                                    let l17 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                    let l19 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                    let l21 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base23 = l21;
+                                    let len23 = l22;
+                                    _rt::cabi_dealloc(base23, len23 * 1, 1);
+                                    let l24 = i32::from(
+                                        *base
+                                            .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l24 {
+                                        0 => {}
+                                        _ => {
+                                            let l25 = *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l26 = *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base27 = l25;
+                                            let len27 = l26;
+                                            _rt::cabi_dealloc(base27, len27 * 1, 1);
+                                            let l28 = *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l29 = *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l28, l29, 1);
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base30,
+                        len30 * (24 + 12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_on_reply_cabi<T: Guest>(arg0: *mut u8) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len2 = l1;
+                    let bytes2 = _rt::Vec::from_raw_parts(l0.cast(), len2, len2);
+                    let l3 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l4 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len5 = l4;
+                    let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+                    let l6 = *arg0
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<i64>();
+                    let l7 = *arg0
+                        .add(8 + 4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l8 = *arg0
+                        .add(8 + 5 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len9 = l8;
+                    let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                    let l10 = i32::from(
+                        *arg0
+                            .add(8 + 6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l14 = *arg0
+                        .add(8 + 9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l15 = *arg0
+                        .add(8 + 10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len16 = l15;
+                    let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+                    let l17 = i32::from(
+                        *arg0
+                            .add(8 + 11 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l18 = i32::from(
+                        *arg0
+                            .add(8 + 12 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l22 = i32::from(
+                        *arg0
+                            .add(8 + 15 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l26 = i32::from(
+                        *arg0
+                            .add(8 + 18 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l30 = *arg0
+                        .add(16 + 20 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l31 = *arg0
+                        .add(16 + 21 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len32 = l31;
+                    let bytes32 = _rt::Vec::from_raw_parts(l30.cast(), len32, len32);
+                    let result33 = T::on_reply(
+                        InboundMessageV1 {
+                            content: _rt::string_lift(bytes2),
+                            event_id: _rt::string_lift(bytes5),
+                            timestamp: l6 as u64,
+                            sender_id: _rt::string_lift(bytes9),
+                            sender_name: match l10 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l11 = *arg0
+                                            .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l12 = *arg0
+                                            .add(8 + 8 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len13 = l12;
+                                        let bytes13 = _rt::Vec::from_raw_parts(
+                                            l11.cast(),
+                                            len13,
+                                            len13,
+                                        );
+                                        _rt::string_lift(bytes13)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            room: _rt::string_lift(bytes16),
+                            is_direct: _rt::bool_lift(l17 as u8),
+                            thread_root: match l18 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l19 = *arg0
+                                            .add(8 + 13 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l20 = *arg0
+                                            .add(8 + 14 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len21 = l20;
+                                        let bytes21 = _rt::Vec::from_raw_parts(
+                                            l19.cast(),
+                                            len21,
+                                            len21,
+                                        );
+                                        _rt::string_lift(bytes21)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            reply_to: match l22 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l23 = *arg0
+                                            .add(8 + 16 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l24 = *arg0
+                                            .add(8 + 17 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len25 = l24;
+                                        let bytes25 = _rt::Vec::from_raw_parts(
+                                            l23.cast(),
+                                            len25,
+                                            len25,
+                                        );
+                                        _rt::string_lift(bytes25)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            edit_of: match l26 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l27 = *arg0
+                                            .add(8 + 19 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l28 = *arg0
+                                            .add(8 + 20 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len29 = l28;
+                                        let bytes29 = _rt::Vec::from_raw_parts(
+                                            l27.cast(),
+                                            len29,
+                                            len29,
+                                        );
+                                        _rt::string_lift(bytes29)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                        },
+                        _rt::string_lift(bytes32),
+                    );
+                    _rt::cabi_dealloc(
+                        arg0,
+                        16 + 22 * ::core::mem::size_of::<*const u8>(),
+                        8,
+                    );
+                    let ptr34 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec49 = result33;
+                    let len49 = vec49.len();
+                    let layout49 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec49.len() * (24 + 12 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result49 = if layout49.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout49).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout49);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec49.into_iter().enumerate() {
+                        let base = result49
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            match e {
+                                Action::Respond(e) => {
+                                    *base.add(0).cast::<u8>() = (0i32) as u8;
+                                    let Message {
+                                        text: text35,
+                                        format: format35,
+                                        to: to35,
+                                        mentions: mentions35,
+                                        mention_room: mention_room35,
+                                    } = e;
+                                    let vec36 = (text35.into_bytes()).into_boxed_slice();
+                                    let ptr36 = vec36.as_ptr().cast::<u8>();
+                                    let len36 = vec36.len();
+                                    ::core::mem::forget(vec36);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len36;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr36.cast_mut();
+                                    match format35 {
+                                        MessageFormat::Plain => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                        MessageFormat::Markdown => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                        }
+                                        MessageFormat::Html(e) => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (2i32) as u8;
+                                            let vec37 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr37 = vec37.as_ptr().cast::<u8>();
+                                            let len37 = vec37.len();
+                                            ::core::mem::forget(vec37);
+                                            *base
+                                                .add(5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len37;
+                                            *base
+                                                .add(4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr37.cast_mut();
+                                        }
+                                    }
+                                    let vec38 = (to35.into_bytes()).into_boxed_slice();
+                                    let ptr38 = vec38.as_ptr().cast::<u8>();
+                                    let len38 = vec38.len();
+                                    ::core::mem::forget(vec38);
+                                    *base
+                                        .add(7 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len38;
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr38.cast_mut();
+                                    let vec40 = mentions35;
+                                    let len40 = vec40.len();
+                                    let layout40 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec40.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result40 = if layout40.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout40).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout40);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec40.into_iter().enumerate() {
+                                        let base = result40
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let vec39 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr39 = vec39.as_ptr().cast::<u8>();
+                                            let len39 = vec39.len();
+                                            ::core::mem::forget(vec39);
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len39;
+                                            *base.add(0).cast::<*mut u8>() = ptr39.cast_mut();
+                                        }
+                                    }
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len40;
+                                    *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result40;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (match mention_room35 {
+                                        true => 1,
+                                        false => 0,
+                                    }) as u8;
+                                }
+                                Action::React(e) => {
+                                    *base.add(0).cast::<u8>() = (1i32) as u8;
+                                    let vec41 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr41 = vec41.as_ptr().cast::<u8>();
+                                    let len41 = vec41.len();
+                                    ::core::mem::forget(vec41);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len41;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr41.cast_mut();
+                                }
+                                Action::Attach(e) => {
+                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                    let Attachment {
+                                        filename: filename42,
+                                        mimetype: mimetype42,
+                                        data: data42,
+                                        width: width42,
+                                        height: height42,
+                                        thumbnail: thumbnail42,
+                                    } = e;
+                                    let vec43 = (filename42.into_bytes()).into_boxed_slice();
+                                    let ptr43 = vec43.as_ptr().cast::<u8>();
+                                    let len43 = vec43.len();
+                                    ::core::mem::forget(vec43);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len43;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr43.cast_mut();
+                                    let vec44 = (mimetype42.into_bytes()).into_boxed_slice();
+                                    let ptr44 = vec44.as_ptr().cast::<u8>();
+                                    let len44 = vec44.len();
+                                    ::core::mem::forget(vec44);
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len44;
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr44.cast_mut();
+                                    let vec45 = (data42).into_boxed_slice();
+                                    let ptr45 = vec45.as_ptr().cast::<u8>();
+                                    let len45 = vec45.len();
+                                    ::core::mem::forget(vec45);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len45;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr45.cast_mut();
+                                    match width42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(4 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match height42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(12 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match thumbnail42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Thumbnail {
+                                                data: data46,
+                                                mimetype: mimetype46,
+                                                width: width46,
+                                                height: height46,
+                                            } = e;
+                                            let vec47 = (data46).into_boxed_slice();
+                                            let ptr47 = vec47.as_ptr().cast::<u8>();
+                                            let len47 = vec47.len();
+                                            ::core::mem::forget(vec47);
+                                            *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len47;
+                                            *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr47.cast_mut();
+                                            let vec48 = (mimetype46.into_bytes()).into_boxed_slice();
+                                            let ptr48 = vec48.as_ptr().cast::<u8>();
+                                            let len48 = vec48.len();
+                                            ::core::mem::forget(vec48);
+                                            *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len48;
+                                            *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr48.cast_mut();
+                                            *base
+                                                .add(16 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(width46);
+                                            *base
+                                                .add(20 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(height46);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
                             }
                         }
                     }
-                    *ptr4.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len10;
-                    *ptr4.add(0).cast::<*mut u8>() = result10;
-                    ptr4
+                    *ptr34.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len49;
+                    *ptr34.add(0).cast::<*mut u8>() = result49;
+                    ptr34
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_admin<T: Guest>(arg0: *mut u8) {
+                pub unsafe fn __post_return_on_reply<T: Guest>(arg0: *mut u8) {
                     let l0 = *arg0.add(0).cast::<*mut u8>();
                     let l1 = *arg0
                         .add(::core::mem::size_of::<*const u8>())
                         .cast::<usize>();
-                    let base12 = l0;
-                    let len12 = l1;
-                    for i in 0..len12 {
-                        let base = base12
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    let base30 = l0;
+                    let len30 = l1;
+                    for i in 0..len30 {
+                        let base = base30
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
                         {
                             let l2 = i32::from(*base.add(0).cast::<u8>());
                             match l2 {
@@ -294,6 +2368,7 @@ pub mod exports {
                                     );
                                     match l5 {
                                         0 => {}
+                                        1 => {}
                                         _ => {
                                             let l6 = *base
                                                 .add(4 * ::core::mem::size_of::<*const u8>())
@@ -311,151 +2386,540 @@ pub mod exports {
                                         .add(7 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
                                     _rt::cabi_dealloc(l8, l9, 1);
+                                    let l10 = *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base14 = l10;
+                                    let len14 = l11;
+                                    for i in 0..len14 {
+                                        let base = base14
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let l12 = *base.add(0).cast::<*mut u8>();
+                                            let l13 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base14,
+                                        len14 * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                }
+                                1 => {
+                                    let l15 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
                                 }
                                 _ => {
-                                    let l10 = *base
+                                    let l17 = *base
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l11 = *base
+                                    let l18 = *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l10, l11, 1);
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                    let l19 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                    let l21 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base23 = l21;
+                                    let len23 = l22;
+                                    _rt::cabi_dealloc(base23, len23 * 1, 1);
+                                    let l24 = i32::from(
+                                        *base
+                                            .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l24 {
+                                        0 => {}
+                                        _ => {
+                                            let l25 = *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l26 = *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base27 = l25;
+                                            let len27 = l26;
+                                            _rt::cabi_dealloc(base27, len27 * 1, 1);
+                                            let l28 = *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l29 = *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l28, l29, 1);
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                     _rt::cabi_dealloc(
-                        base12,
-                        len12 * (8 * ::core::mem::size_of::<*const u8>()),
+                        base30,
+                        len30 * (24 + 12 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_on_msg_cabi<T: Guest>(
-                    arg0: *mut u8,
-                    arg1: usize,
-                    arg2: *mut u8,
-                    arg3: usize,
-                    arg4: *mut u8,
-                    arg5: usize,
-                    arg6: *mut u8,
-                    arg7: usize,
-                ) -> *mut u8 {
+                pub unsafe fn _export_on_edit_cabi<T: Guest>(arg0: *mut u8) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg1;
-                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
-                    let len1 = arg3;
-                    let bytes1 = _rt::Vec::from_raw_parts(arg2.cast(), len1, len1);
-                    let len2 = arg5;
-                    let bytes2 = _rt::Vec::from_raw_parts(arg4.cast(), len2, len2);
-                    let len3 = arg7;
-                    let bytes3 = _rt::Vec::from_raw_parts(arg6.cast(), len3, len3);
-                    let result4 = T::on_msg(
-                        _rt::string_lift(bytes0),
-                        _rt::string_lift(bytes1),
-                        _rt::string_lift(bytes2),
-                        _rt::string_lift(bytes3),
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len2 = l1;
+                    let bytes2 = _rt::Vec::from_raw_parts(l0.cast(), len2, len2);
+                    let l3 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l4 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len5 = l4;
+                    let bytes5 = _rt::Vec::from_raw_parts(l3.cast(), len5, len5);
+                    let l6 = *arg0
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<i64>();
+                    let l7 = *arg0
+                        .add(8 + 4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l8 = *arg0
+                        .add(8 + 5 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len9 = l8;
+                    let bytes9 = _rt::Vec::from_raw_parts(l7.cast(), len9, len9);
+                    let l10 = i32::from(
+                        *arg0
+                            .add(8 + 6 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
                     );
-                    let ptr5 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec11 = result4;
-                    let len11 = vec11.len();
-                    let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
-                        vec11.len() * (8 * ::core::mem::size_of::<*const u8>()),
+                    let l14 = *arg0
+                        .add(8 + 9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l15 = *arg0
+                        .add(8 + 10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len16 = l15;
+                    let bytes16 = _rt::Vec::from_raw_parts(l14.cast(), len16, len16);
+                    let l17 = i32::from(
+                        *arg0
+                            .add(8 + 11 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l18 = i32::from(
+                        *arg0
+                            .add(8 + 12 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l22 = i32::from(
+                        *arg0
+                            .add(8 + 15 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l26 = i32::from(
+                        *arg0
+                            .add(8 + 18 * ::core::mem::size_of::<*const u8>())
+                            .cast::<u8>(),
+                    );
+                    let l30 = *arg0
+                        .add(16 + 20 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l31 = *arg0
+                        .add(16 + 21 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let len32 = l31;
+                    let bytes32 = _rt::Vec::from_raw_parts(l30.cast(), len32, len32);
+                    let result33 = T::on_edit(
+                        InboundMessageV1 {
+                            content: _rt::string_lift(bytes2),
+                            event_id: _rt::string_lift(bytes5),
+                            timestamp: l6 as u64,
+                            sender_id: _rt::string_lift(bytes9),
+                            sender_name: match l10 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l11 = *arg0
+                                            .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l12 = *arg0
+                                            .add(8 + 8 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len13 = l12;
+                                        let bytes13 = _rt::Vec::from_raw_parts(
+                                            l11.cast(),
+                                            len13,
+                                            len13,
+                                        );
+                                        _rt::string_lift(bytes13)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            room: _rt::string_lift(bytes16),
+                            is_direct: _rt::bool_lift(l17 as u8),
+                            thread_root: match l18 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l19 = *arg0
+                                            .add(8 + 13 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l20 = *arg0
+                                            .add(8 + 14 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len21 = l20;
+                                        let bytes21 = _rt::Vec::from_raw_parts(
+                                            l19.cast(),
+                                            len21,
+                                            len21,
+                                        );
+                                        _rt::string_lift(bytes21)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            reply_to: match l22 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l23 = *arg0
+                                            .add(8 + 16 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l24 = *arg0
+                                            .add(8 + 17 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len25 = l24;
+                                        let bytes25 = _rt::Vec::from_raw_parts(
+                                            l23.cast(),
+                                            len25,
+                                            len25,
+                                        );
+                                        _rt::string_lift(bytes25)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                            edit_of: match l26 {
+                                0 => None,
+                                1 => {
+                                    let e = {
+                                        let l27 = *arg0
+                                            .add(8 + 19 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<*mut u8>();
+                                        let l28 = *arg0
+                                            .add(8 + 20 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len29 = l28;
+                                        let bytes29 = _rt::Vec::from_raw_parts(
+                                            l27.cast(),
+                                            len29,
+                                            len29,
+                                        );
+                                        _rt::string_lift(bytes29)
+                                    };
+                                    Some(e)
+                                }
+                                _ => _rt::invalid_enum_discriminant(),
+                            },
+                        },
+                        _rt::string_lift(bytes32),
+                    );
+                    _rt::cabi_dealloc(
+                        arg0,
+                        16 + 22 * ::core::mem::size_of::<*const u8>(),
+                        8,
+                    );
+                    let ptr34 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec49 = result33;
+                    let len49 = vec49.len();
+                    let layout49 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec49.len() * (24 + 12 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
-                    let result11 = if layout11.size() != 0 {
-                        let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                    let result49 = if layout49.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout49).cast::<u8>();
                         if ptr.is_null() {
-                            _rt::alloc::handle_alloc_error(layout11);
+                            _rt::alloc::handle_alloc_error(layout49);
                         }
                         ptr
                     } else {
                         ::core::ptr::null_mut()
                     };
-                    for (i, e) in vec11.into_iter().enumerate() {
-                        let base = result11
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    for (i, e) in vec49.into_iter().enumerate() {
+                        let base = result49
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
                         {
                             match e {
                                 Action::Respond(e) => {
                                     *base.add(0).cast::<u8>() = (0i32) as u8;
-                                    let Message { text: text6, html: html6, to: to6 } = e;
-                                    let vec7 = (text6.into_bytes()).into_boxed_slice();
-                                    let ptr7 = vec7.as_ptr().cast::<u8>();
-                                    let len7 = vec7.len();
-                                    ::core::mem::forget(vec7);
+                                    let Message {
+                                        text: text35,
+                                        format: format35,
+                                        to: to35,
+                                        mentions: mentions35,
+                                        mention_room: mention_room35,
+                                    } = e;
+                                    let vec36 = (text35.into_bytes()).into_boxed_slice();
+                                    let ptr36 = vec36.as_ptr().cast::<u8>();
+                                    let len36 = vec36.len();
+                                    ::core::mem::forget(vec36);
                                     *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len7;
+                                        .cast::<usize>() = len36;
                                     *base
                                         .add(::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr7.cast_mut();
-                                    match html6 {
-                                        Some(e) => {
+                                        .cast::<*mut u8>() = ptr36.cast_mut();
+                                    match format35 {
+                                        MessageFormat::Plain => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                        MessageFormat::Markdown => {
                                             *base
                                                 .add(3 * ::core::mem::size_of::<*const u8>())
                                                 .cast::<u8>() = (1i32) as u8;
-                                            let vec8 = (e.into_bytes()).into_boxed_slice();
-                                            let ptr8 = vec8.as_ptr().cast::<u8>();
-                                            let len8 = vec8.len();
-                                            ::core::mem::forget(vec8);
+                                        }
+                                        MessageFormat::Html(e) => {
+                                            *base
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (2i32) as u8;
+                                            let vec37 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr37 = vec37.as_ptr().cast::<u8>();
+                                            let len37 = vec37.len();
+                                            ::core::mem::forget(vec37);
                                             *base
                                                 .add(5 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<usize>() = len8;
+                                                .cast::<usize>() = len37;
                                             *base
                                                 .add(4 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<*mut u8>() = ptr8.cast_mut();
-                                        }
-                                        None => {
-                                            *base
-                                                .add(3 * ::core::mem::size_of::<*const u8>())
-                                                .cast::<u8>() = (0i32) as u8;
+                                                .cast::<*mut u8>() = ptr37.cast_mut();
                                         }
-                                    };
-                                    let vec9 = (to6.into_bytes()).into_boxed_slice();
-                                    let ptr9 = vec9.as_ptr().cast::<u8>();
-                                    let len9 = vec9.len();
-                                    ::core::mem::forget(vec9);
+                                    }
+                                    let vec38 = (to35.into_bytes()).into_boxed_slice();
+                                    let ptr38 = vec38.as_ptr().cast::<u8>();
+                                    let len38 = vec38.len();
+                                    ::core::mem::forget(vec38);
                                     *base
                                         .add(7 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len9;
+                                        .cast::<usize>() = len38;
                                     *base
                                         .add(6 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr9.cast_mut();
+                                        .cast::<*mut u8>() = ptr38.cast_mut();
+                                    let vec40 = mentions35;
+                                    let len40 = vec40.len();
+                                    let layout40 = _rt::alloc::Layout::from_size_align_unchecked(
+                                        vec40.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                    let result40 = if layout40.size() != 0 {
+                                        let ptr = _rt::alloc::alloc(layout40).cast::<u8>();
+                                        if ptr.is_null() {
+                                            _rt::alloc::handle_alloc_error(layout40);
+                                        }
+                                        ptr
+                                    } else {
+                                        ::core::ptr::null_mut()
+                                    };
+                                    for (i, e) in vec40.into_iter().enumerate() {
+                                        let base = result40
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let vec39 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr39 = vec39.as_ptr().cast::<u8>();
+                                            let len39 = vec39.len();
+                                            ::core::mem::forget(vec39);
+                                            *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len39;
+                                            *base.add(0).cast::<*mut u8>() = ptr39.cast_mut();
+                                        }
+                                    }
+                                    *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len40;
+                                    *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = result40;
+                                    *base
+                                        .add(10 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (match mention_room35 {
+                                        true => 1,
+                                        false => 0,
+                                    }) as u8;
                                 }
                                 Action::React(e) => {
                                     *base.add(0).cast::<u8>() = (1i32) as u8;
-                                    let vec10 = (e.into_bytes()).into_boxed_slice();
-                                    let ptr10 = vec10.as_ptr().cast::<u8>();
-                                    let len10 = vec10.len();
-                                    ::core::mem::forget(vec10);
+                                    let vec41 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr41 = vec41.as_ptr().cast::<u8>();
+                                    let len41 = vec41.len();
+                                    ::core::mem::forget(vec41);
                                     *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
-                                        .cast::<usize>() = len10;
+                                        .cast::<usize>() = len41;
                                     *base
                                         .add(::core::mem::size_of::<*const u8>())
-                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                        .cast::<*mut u8>() = ptr41.cast_mut();
+                                }
+                                Action::Attach(e) => {
+                                    *base.add(0).cast::<u8>() = (2i32) as u8;
+                                    let Attachment {
+                                        filename: filename42,
+                                        mimetype: mimetype42,
+                                        data: data42,
+                                        width: width42,
+                                        height: height42,
+                                        thumbnail: thumbnail42,
+                                    } = e;
+                                    let vec43 = (filename42.into_bytes()).into_boxed_slice();
+                                    let ptr43 = vec43.as_ptr().cast::<u8>();
+                                    let len43 = vec43.len();
+                                    ::core::mem::forget(vec43);
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len43;
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr43.cast_mut();
+                                    let vec44 = (mimetype42.into_bytes()).into_boxed_slice();
+                                    let ptr44 = vec44.as_ptr().cast::<u8>();
+                                    let len44 = vec44.len();
+                                    ::core::mem::forget(vec44);
+                                    *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len44;
+                                    *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr44.cast_mut();
+                                    let vec45 = (data42).into_boxed_slice();
+                                    let ptr45 = vec45.as_ptr().cast::<u8>();
+                                    let len45 = vec45.len();
+                                    ::core::mem::forget(vec45);
+                                    *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len45;
+                                    *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr45.cast_mut();
+                                    match width42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(4 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match height42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(12 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(8 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match thumbnail42 {
+                                        Some(e) => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Thumbnail {
+                                                data: data46,
+                                                mimetype: mimetype46,
+                                                width: width46,
+                                                height: height46,
+                                            } = e;
+                                            let vec47 = (data46).into_boxed_slice();
+                                            let ptr47 = vec47.as_ptr().cast::<u8>();
+                                            let len47 = vec47.len();
+                                            ::core::mem::forget(vec47);
+                                            *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len47;
+                                            *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr47.cast_mut();
+                                            let vec48 = (mimetype46.into_bytes()).into_boxed_slice();
+                                            let ptr48 = vec48.as_ptr().cast::<u8>();
+                                            let len48 = vec48.len();
+                                            ::core::mem::forget(vec48);
+                                            *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len48;
+                                            *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr48.cast_mut();
+                                            *base
+                                                .add(16 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(width46);
+                                            *base
+                                                .add(20 + 12 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(height46);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
                                 }
                             }
                         }
                     }
-                    *ptr5.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len11;
-                    *ptr5.add(0).cast::<*mut u8>() = result11;
-                    ptr5
+                    *ptr34.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len49;
+                    *ptr34.add(0).cast::<*mut u8>() = result49;
+                    ptr34
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_on_msg<T: Guest>(arg0: *mut u8) {
+                pub unsafe fn __post_return_on_edit<T: Guest>(arg0: *mut u8) {
                     let l0 = *arg0.add(0).cast::<*mut u8>();
                     let l1 = *arg0
                         .add(::core::mem::size_of::<*const u8>())
                         .cast::<usize>();
-                    let base12 = l0;
-                    let len12 = l1;
-                    for i in 0..len12 {
-                        let base = base12
-                            .add(i * (8 * ::core::mem::size_of::<*const u8>()));
+                    let base30 = l0;
+                    let len30 = l1;
+                    for i in 0..len30 {
+                        let base = base30
+                            .add(i * (24 + 12 * ::core::mem::size_of::<*const u8>()));
                         {
                             let l2 = i32::from(*base.add(0).cast::<u8>());
                             match l2 {
@@ -474,6 +2938,7 @@ pub mod exports {
                                     );
                                     match l5 {
                                         0 => {}
+                                        1 => {}
                                         _ => {
                                             let l6 = *base
                                                 .add(4 * ::core::mem::size_of::<*const u8>())
@@ -491,38 +2956,151 @@ pub mod exports {
                                         .add(7 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
                                     _rt::cabi_dealloc(l8, l9, 1);
+                                    let l10 = *base
+                                        .add(8 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *base
+                                        .add(9 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base14 = l10;
+                                    let len14 = l11;
+                                    for i in 0..len14 {
+                                        let base = base14
+                                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                                        {
+                                            let l12 = *base.add(0).cast::<*mut u8>();
+                                            let l13 = *base
+                                                .add(::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l12, l13, 1);
+                                        }
+                                    }
+                                    _rt::cabi_dealloc(
+                                        base14,
+                                        len14 * (2 * ::core::mem::size_of::<*const u8>()),
+                                        ::core::mem::size_of::<*const u8>(),
+                                    );
+                                }
+                                1 => {
+                                    let l15 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
                                 }
                                 _ => {
-                                    let l10 = *base
+                                    let l17 = *base
                                         .add(::core::mem::size_of::<*const u8>())
                                         .cast::<*mut u8>();
-                                    let l11 = *base
+                                    let l18 = *base
                                         .add(2 * ::core::mem::size_of::<*const u8>())
                                         .cast::<usize>();
-                                    _rt::cabi_dealloc(l10, l11, 1);
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                    let l19 = *base
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *base
+                                        .add(4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                    let l21 = *base
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *base
+                                        .add(6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base23 = l21;
+                                    let len23 = l22;
+                                    _rt::cabi_dealloc(base23, len23 * 1, 1);
+                                    let l24 = i32::from(
+                                        *base
+                                            .add(16 + 7 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l24 {
+                                        0 => {}
+                                        _ => {
+                                            let l25 = *base
+                                                .add(16 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l26 = *base
+                                                .add(16 + 9 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let base27 = l25;
+                                            let len27 = l26;
+                                            _rt::cabi_dealloc(base27, len27 * 1, 1);
+                                            let l28 = *base
+                                                .add(16 + 10 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l29 = *base
+                                                .add(16 + 11 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l28, l29, 1);
+                                        }
+                                    }
                                 }
                             }
                         }
                     }
                     _rt::cabi_dealloc(
-                        base12,
-                        len12 * (8 * ::core::mem::size_of::<*const u8>()),
+                        base30,
+                        len30 * (24 + 12 * ::core::mem::size_of::<*const u8>()),
                         ::core::mem::size_of::<*const u8>(),
                     );
                 }
                 pub trait Guest {
-                    fn init(config: Option<_rt::Vec<(_rt::String, _rt::String)>>) -> ();
+                    /// Describes the module: name, version, commands, capabilities, etc.
+                    ///
+                    /// The module's configuration is given like for `init`, so that e.g. its triggers can depend
+                    /// on it.
+                    fn manifest(config: Option<_rt::String>) -> ModuleManifest;
+                    /// Names of the data migrations this module declares, in order of application.
+                    ///
+                    /// The host remembers how many of them already ran, and calls `migrate` for each new one,
+                    /// in order, before calling `init`.
+                    fn migrations() -> _rt::Vec<_rt::String>;
+                    /// Runs the data migration with the given name.
+                    ///
+                    /// The host records that a migration ran only once it returned, so a migration can run
+                    /// again if the bot stops in the meanwhile: migrations must be idempotent.
+                    fn migrate(name: _rt::String) -> Result<(), _rt::String>;
+                    /// Initializes the module with its configuration, serialized as a JSON object, if the
+                    /// module is configured.
+                    ///
+                    /// Returning an error, e.g. in case of invalid configuration, prevents the module from
+                    /// being loaded.
+                    fn init(config: Option<_rt::String>) -> Result<(), _rt::String>;
+                    /// Called before the module is unloaded, replaced by a new version, or when the bot exits.
+                    ///
+                    /// This is the last chance to flush any in-memory state, e.g. to the key-value store. If the
+                    /// new version of the module fails to initialize, `init` is called again on this instance,
+                    /// which keeps running.
+                    fn shutdown() -> ();
                     fn help(topic: Option<_rt::String>) -> _rt::String;
                     fn admin(
                         cmd: _rt::String,
                         author_id: _rt::String,
                         room: _rt::String,
                     ) -> _rt::Vec<Action>;
-                    fn on_msg(
-                        content: _rt::String,
-                        author_id: _rt::String,
-                        author_name: _rt::String,
-                        room: _rt::String,
+                    fn on_msg(msg: InboundMessageV1) -> _rt::Vec<Action>;
+                    /// Handles a reply to a message sent by this module, or a message in a thread started by
+                    /// one; `in-reply-to` is the ID of the module's message.
+                    ///
+                    /// Returning no actions lets the message go through the usual dispatch.
+                    fn on_reply(
+                        msg: InboundMessageV1,
+                        in_reply_to: _rt::String,
+                    ) -> _rt::Vec<Action>;
+                    /// Handles an edit of a message this module responded to; `original` is the ID of the
+                    /// edited message, and `msg` contains its new content.
+                    ///
+                    /// Messages sent in response replace the ones sent for the original message, in order.
+                    /// Returning no actions ignores the edit.
+                    fn on_edit(
+                        msg: InboundMessageV1,
+                        original: _rt::String,
                     ) -> _rt::Vec<Action>;
                 }
                 #[doc(hidden)]
@@ -530,16 +3108,44 @@ pub mod exports {
                 macro_rules! __export_trinity_module_messaging_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = { #[unsafe (export_name =
+                        "trinity:module/messaging#manifest")] unsafe extern "C" fn
+                        export_manifest(arg0 : i32, arg1 : * mut u8, arg2 : usize,) -> *
+                        mut u8 { unsafe { $($path_to_types)*::
+                        _export_manifest_cabi::<$ty > (arg0, arg1, arg2) } } #[unsafe
+                        (export_name = "cabi_post_trinity:module/messaging#manifest")]
+                        unsafe extern "C" fn _post_return_manifest(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_manifest::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "trinity:module/messaging#migrations")] unsafe extern "C" fn
+                        export_migrations() -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_migrations_cabi::<$ty > () } } #[unsafe (export_name =
+                        "cabi_post_trinity:module/messaging#migrations")] unsafe extern
+                        "C" fn _post_return_migrations(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_migrations::<$ty > (arg0) } }
+                        #[unsafe (export_name = "trinity:module/messaging#migrate")]
+                        unsafe extern "C" fn export_migrate(arg0 : * mut u8, arg1 :
+                        usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_migrate_cabi::<$ty > (arg0, arg1) } } #[unsafe
+                        (export_name = "cabi_post_trinity:module/messaging#migrate")]
+                        unsafe extern "C" fn _post_return_migrate(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*:: __post_return_migrate::<$ty >
+                        (arg0) } } #[unsafe (export_name =
                         "trinity:module/messaging#init")] unsafe extern "C" fn
-                        export_init(arg0 : i32, arg1 : * mut u8, arg2 : usize,) { unsafe
-                        { $($path_to_types)*:: _export_init_cabi::<$ty > (arg0, arg1,
-                        arg2) } } #[unsafe (export_name =
-                        "trinity:module/messaging#help")] unsafe extern "C" fn
-                        export_help(arg0 : i32, arg1 : * mut u8, arg2 : usize,) -> * mut
-                        u8 { unsafe { $($path_to_types)*:: _export_help_cabi::<$ty >
+                        export_init(arg0 : i32, arg1 : * mut u8, arg2 : usize,) -> * mut
+                        u8 { unsafe { $($path_to_types)*:: _export_init_cabi::<$ty >
                         (arg0, arg1, arg2) } } #[unsafe (export_name =
-                        "cabi_post_trinity:module/messaging#help")] unsafe extern "C" fn
-                        _post_return_help(arg0 : * mut u8,) { unsafe {
+                        "cabi_post_trinity:module/messaging#init")] unsafe extern "C" fn
+                        _post_return_init(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_init::<$ty > (arg0) } }
+                        #[unsafe (export_name = "trinity:module/messaging#shutdown")]
+                        unsafe extern "C" fn export_shutdown() { unsafe {
+                        $($path_to_types)*:: _export_shutdown_cabi::<$ty > () } }
+                        #[unsafe (export_name = "trinity:module/messaging#help")] unsafe
+                        extern "C" fn export_help(arg0 : i32, arg1 : * mut u8, arg2 :
+                        usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_help_cabi::<$ty > (arg0, arg1, arg2) } } #[unsafe
+                        (export_name = "cabi_post_trinity:module/messaging#help")] unsafe
+                        extern "C" fn _post_return_help(arg0 : * mut u8,) { unsafe {
                         $($path_to_types)*:: __post_return_help::<$ty > (arg0) } }
                         #[unsafe (export_name = "trinity:module/messaging#admin")] unsafe
                         extern "C" fn export_admin(arg0 : * mut u8, arg1 : usize, arg2 :
@@ -550,14 +3156,26 @@ pub mod exports {
                         _post_return_admin(arg0 : * mut u8,) { unsafe {
                         $($path_to_types)*:: __post_return_admin::<$ty > (arg0) } }
                         #[unsafe (export_name = "trinity:module/messaging#on-msg")]
-                        unsafe extern "C" fn export_on_msg(arg0 : * mut u8, arg1 : usize,
-                        arg2 : * mut u8, arg3 : usize, arg4 : * mut u8, arg5 : usize,
-                        arg6 : * mut u8, arg7 : usize,) -> * mut u8 { unsafe {
-                        $($path_to_types)*:: _export_on_msg_cabi::<$ty > (arg0, arg1,
-                        arg2, arg3, arg4, arg5, arg6, arg7) } } #[unsafe (export_name =
+                        unsafe extern "C" fn export_on_msg(arg0 : * mut u8,) -> * mut u8
+                        { unsafe { $($path_to_types)*:: _export_on_msg_cabi::<$ty >
+                        (arg0) } } #[unsafe (export_name =
                         "cabi_post_trinity:module/messaging#on-msg")] unsafe extern "C"
                         fn _post_return_on_msg(arg0 : * mut u8,) { unsafe {
-                        $($path_to_types)*:: __post_return_on_msg::<$ty > (arg0) } } };
+                        $($path_to_types)*:: __post_return_on_msg::<$ty > (arg0) } }
+                        #[unsafe (export_name = "trinity:module/messaging#on-reply")]
+                        unsafe extern "C" fn export_on_reply(arg0 : * mut u8,) -> * mut
+                        u8 { unsafe { $($path_to_types)*:: _export_on_reply_cabi::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "cabi_post_trinity:module/messaging#on-reply")] unsafe extern "C"
+                        fn _post_return_on_reply(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_on_reply::<$ty > (arg0) } }
+                        #[unsafe (export_name = "trinity:module/messaging#on-edit")]
+                        unsafe extern "C" fn export_on_edit(arg0 : * mut u8,) -> * mut u8
+                        { unsafe { $($path_to_types)*:: _export_on_edit_cabi::<$ty >
+                        (arg0) } } #[unsafe (export_name =
+                        "cabi_post_trinity:module/messaging#on-edit")] unsafe extern "C"
+                        fn _post_return_on_edit(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_on_edit::<$ty > (arg0) } } };
                     };
                 }
                 #[doc(hidden)]
@@ -567,10 +3185,10 @@ pub mod exports {
                 struct _RetArea(
                     [::core::mem::MaybeUninit<
                         u8,
-                    >; 2 * ::core::mem::size_of::<*const u8>()],
+                    >; 15 * ::core::mem::size_of::<*const u8>()],
                 );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 2
+                    [::core::mem::MaybeUninit::uninit(); 15
                         * ::core::mem::size_of::<*const u8>()],
                 );
             }
@@ -581,11 +3199,11 @@ pub mod exports {
 mod _rt {
     #![allow(dead_code, clippy::all)]
     pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
     #[cfg(target_arch = "wasm32")]
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
     }
-    pub use alloc_crate::vec::Vec;
     pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
         if cfg!(debug_assertions) {
             String::from_utf8(bytes).unwrap()
@@ -593,6 +3211,14 @@ mod _rt {
             String::from_utf8_unchecked(bytes)
         }
     }
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            unsafe { core::hint::unreachable_unchecked() }
+        }
+    }
+    pub use alloc_crate::alloc;
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -600,14 +3226,76 @@ mod _rt {
         let layout = alloc::Layout::from_size_align_unchecked(size, align);
         alloc::dealloc(ptr, layout);
     }
-    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    pub unsafe fn bool_lift(val: u8) -> bool {
         if cfg!(debug_assertions) {
-            panic!("invalid enum discriminant")
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
         } else {
-            unsafe { core::hint::unreachable_unchecked() }
+            val != 0
         }
     }
-    pub use alloc_crate::alloc;
     extern crate alloc as alloc_crate;
 }
 /// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
@@ -640,18 +3328,34 @@ macro_rules! __export_trinity_module_impl {
         const _ : () = { #[cfg(target_arch = "wasm32")] #[unsafe (link_section =
         "component-type:wit-bindgen:0.41.0:trinity:module:trinity-module:imports and exports")]
         #[doc(hidden)] #[allow(clippy::octal_escapes)] pub static
-        __WIT_BINDGEN_COMPONENT_TYPE : [u8; 440] = *
+        __WIT_BINDGEN_COMPONENT_TYPE : [u8; 1176] = *
         b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb3\x02\x01A\x02\x01\
-A\x02\x01B\x13\x01ks\x01r\x03\x04texts\x04html\0\x02tos\x04\0\x07message\x03\0\x01\
-\x01s\x04\0\x08reaction\x03\0\x03\x01q\x02\x07respond\x01\x02\0\x05react\x01\x04\
-\0\x04\0\x06action\x03\0\x05\x01o\x02ss\x01p\x07\x01k\x08\x01@\x01\x06config\x09\
-\x01\0\x04\0\x04init\x01\x0a\x01@\x01\x05topic\0\0s\x04\0\x04help\x01\x0b\x01p\x06\
-\x01@\x03\x03cmds\x09author-ids\x04rooms\0\x0c\x04\0\x05admin\x01\x0d\x01@\x04\x07\
-contents\x09author-ids\x0bauthor-names\x04rooms\0\x0c\x04\0\x06on-msg\x01\x0e\x04\
-\0\x18trinity:module/messaging\x05\0\x04\0\x1dtrinity:module/trinity-module\x04\0\
-\x0b\x14\x01\0\x0etrinity-module\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\
-\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x93\x08\x01A\x02\x01\
+A\x02\x01B4\x01q\x03\x05plain\0\0\x08markdown\0\0\x04html\x01s\0\x04\0\x0emessag\
+e-format\x03\0\0\x01ps\x01r\x05\x04texts\x06format\x01\x02tos\x08mentions\x02\x0c\
+mention-room\x7f\x04\0\x07message\x03\0\x03\x01s\x04\0\x08reaction\x03\0\x05\x01\
+p}\x01r\x04\x04data\x07\x08mimetypes\x05widthy\x06heighty\x04\0\x09thumbnail\x03\
+\0\x08\x01ky\x01k\x09\x01r\x06\x08filenames\x08mimetypes\x04data\x07\x05width\x0a\
+\x06height\x0a\x09thumbnail\x0b\x04\0\x0aattachment\x03\0\x0c\x01q\x03\x07respon\
+d\x01\x04\0\x05react\x01\x06\0\x06attach\x01\x0d\0\x04\0\x06action\x03\0\x0e\x01\
+m\x08\x02kv\x03log\x0csync-request\x03sys\x07history\x09room-info\x0aroom-state\x06\
+typing\x04\0\x0acapability\x03\0\x10\x01q\x03\x06prefix\x01s\0\x05regex\x01s\0\x03\
+all\0\0\x04\0\x07trigger\x03\0\x12\x01ks\x01r\x0a\x07contents\x08event-ids\x09ti\
+mestampw\x09sender-ids\x0bsender-name\x14\x04rooms\x09is-direct\x7f\x0bthread-ro\
+ot\x14\x08reply-to\x14\x07edit-of\x14\x04\0\x12inbound-message-v1\x03\0\x15\x01r\
+\x03\x04names\x0bdescriptions\x08required\x7f\x04\0\x0aconfig-key\x03\0\x17\x01p\
+\x13\x01p\x11\x01p\x18\x01r\x07\x04names\x07versions\x06author\x14\x08commands\x02\
+\x08triggers\x19\x0ccapabilities\x1a\x0dconfig-schema\x1b\x04\0\x0fmodule-manife\
+st\x03\0\x1c\x01@\x01\x06config\x14\0\x1d\x04\0\x08manifest\x01\x1e\x01@\0\0\x02\
+\x04\0\x0amigrations\x01\x1f\x01j\0\x01s\x01@\x01\x04names\0\x20\x04\0\x07migrat\
+e\x01!\x01@\x01\x06config\x14\0\x20\x04\0\x04init\x01\"\x01@\0\x01\0\x04\0\x08sh\
+utdown\x01#\x01@\x01\x05topic\x14\0s\x04\0\x04help\x01$\x01p\x0f\x01@\x03\x03cmd\
+s\x09author-ids\x04rooms\0%\x04\0\x05admin\x01&\x01@\x01\x03msg\x16\0%\x04\0\x06\
+on-msg\x01'\x01@\x02\x03msg\x16\x0bin-reply-tos\0%\x04\0\x08on-reply\x01(\x01@\x02\
+\x03msg\x16\x08originals\0%\x04\0\x07on-edit\x01)\x04\0\x18trinity:module/messag\
+ing\x05\0\x04\0\x1dtrinity:module/trinity-module\x04\0\x0b\x14\x01\0\x0etrinity-\
+module\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.227\
+.1\x10wit-bindgen-rust\x060.41.0";
         };
     };
 }
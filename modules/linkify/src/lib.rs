@@ -1,7 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use anyhow::Context as _;
-use libcommand::{impl_command, CommandClient, TrinityCommand};
+use libcommand::{impl_command, Capability, CommandClient, Manifest, ModuleConfig, TrinityCommand};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use shlex;
@@ -166,10 +166,18 @@ impl Component {
 }
 
 impl TrinityCommand for Component {
-    fn init(_config: HashMap<String, String>) {
+    fn manifest(_config: &ModuleConfig) -> Manifest {
+        libcommand::manifest!()
+            .catch_all()
+            .capability(Capability::Kv)
+            .capability(Capability::Log)
+    }
+
+    fn init(_config: ModuleConfig) -> Result<(), String> {
         let _ = log::set_boxed_logger(Box::new(log::WitLog::new()));
         log::set_max_level(log::LevelFilter::Trace);
         log::trace!("Called the init() method \\o/");
+        Ok(())
     }
 
     fn on_help(topic: Option<&str>) -> String {
@@ -228,10 +236,16 @@ impl TrinityCommand for Component {
 
     fn on_msg(client: &mut CommandClient, content: &str) {
         if let Some(content) = Self::replace(&content, Self::get_room_config(client.room())) {
-            client.respond(content);
+            // Substitutions may produce Markdown links, e.g. `[#123](https://...)`.
+            client.respond_markdown(content);
         }
     }
 
+    fn on_edit(client: &mut CommandClient, content: &str, _original: &str) {
+        // Update the previous answer with the links of the new content.
+        Self::on_msg(client, content);
+    }
+
     fn on_admin(client: &mut CommandClient, cmd: &str) {
         let content = match Self::handle_admin(&cmd, client.from(), client.room()) {
             Ok(resp) => resp,
@@ -1,13 +1,23 @@
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::Context as _;
-use libcommand::{impl_command, CommandClient, TrinityCommand};
+use libcommand::{impl_command, Capability, CommandClient, Manifest, ModuleConfig, TrinityCommand};
 use wit_log as log;
 use wit_sync_request;
 
+/// Whether rooms can be set to the "always" trigger mode, per the `catch_all` config.
+static CATCH_ALL: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+struct Config {
+    catch_all: bool,
+}
+
 #[derive(serde::Serialize, serde::Deserialize)]
 enum TriggerMode {
-    /// Every message will be handled by this bot command, unless another handler caught it first
+    /// Every message will be handled by this bot command, unless another handler caught it first;
+    /// only available when `catch_all` is set in the config.
     Always,
 
     //// Only messages starting with !ai prefix will be handled
@@ -30,13 +40,12 @@ impl Component {
             return Ok(None);
         };
 
-        match config.trigger {
-            TriggerMode::Always => {}
-            TriggerMode::Prefix => {
-                if !content.starts_with("!ai") {
-                    return Ok(None);
-                }
-            }
+        let always = match config.trigger {
+            TriggerMode::Always => CATCH_ALL.load(Ordering::Relaxed),
+            TriggerMode::Prefix => false,
+        };
+        if !always && !content.starts_with("!ai") {
+            return Ok(None);
         }
 
         #[derive(serde::Serialize)]
@@ -97,6 +106,9 @@ impl Component {
             };
 
             let trigger = match trigger.trim() {
+                "always" if !CATCH_ALL.load(Ordering::Relaxed) => {
+                    anyhow::bail!("the 'always' trigger mode requires `catch_all = true` in the module's config")
+                }
                 "always" => TriggerMode::Always,
                 "prefix" => TriggerMode::Prefix,
                 _ => anyhow::bail!("unknown trigger mode, available: 'always' or 'trigger'"),
@@ -120,10 +132,60 @@ impl Component {
     }
 }
 
+/// Rooms set to the "always" trigger mode before `catch_all` was introduced only answer to `!ai`
+/// messages now; warns about them, so the config can be updated.
+fn warn_about_always_rooms() {
+    let rooms = match wit_kv::keys::<String>() {
+        Ok(rooms) => rooms,
+        Err(err) => {
+            log::warn!("couldn't list the configured rooms: {err:#}");
+            return;
+        }
+    };
+    for room in rooms {
+        if let Ok(Some(RoomConfig {
+            trigger: TriggerMode::Always,
+            ..
+        })) = wit_kv::get::<_, RoomConfig>(&room)
+        {
+            log::warn!(
+                "room {room} is in the 'always' trigger mode, but `catch_all` isn't set in the \
+                 config: it will only answer messages starting with !ai"
+            );
+        }
+    }
+}
+
 impl TrinityCommand for Component {
-    fn init(_config: HashMap<String, String>) {
+    fn manifest(config: &ModuleConfig) -> Manifest {
+        let manifest = libcommand::manifest!()
+            .command("!ai")
+            .capability(Capability::Kv)
+            .capability(Capability::Log)
+            .capability(Capability::SyncRequest)
+            .config_key(
+                "catch_all",
+                "whether rooms can be set to answer every message (default: false)",
+                false,
+            );
+
+        // In the "always" trigger mode, every message in the room is answered.
+        match config.parse::<Config>() {
+            Ok(config) if config.catch_all => manifest.catch_all(),
+            _ => manifest,
+        }
+    }
+
+    fn init(config: ModuleConfig) -> Result<(), String> {
         let _ = log::set_boxed_logger(Box::new(crate::log::WitLog::new()));
         log::set_max_level(log::LevelFilter::Trace);
+
+        let config: Config = config.parse()?;
+        CATCH_ALL.store(config.catch_all, Ordering::Relaxed);
+        if !config.catch_all {
+            warn_about_always_rooms();
+        }
+        Ok(())
     }
 
     fn on_help(topic: Option<&str>) -> String {
@@ -132,24 +194,25 @@ impl TrinityCommand for Component {
                 "admin" => r#"available admin commands:
 - enable #TOKEN #TRIGGER_MODE
     where TRIGGER_MODE is either:
-    - 'always' (the bot will answer any message in that room)
+    - 'always' (the bot will answer any message in that room; requires `catch_all = true` in
+      the module's config)
     - 'prefix' (the bot will only handle messages starting with !ai)
 - disable"#
                     .into(),
                 _ => "i don't know this command!".into(),
             }
         } else {
-            "Chat using OpenAI! Will respond to every message given it's configured in a room. Help topics: admin".to_owned()
+            "Chat using OpenAI! Will respond to messages starting with !ai, or to every message if configured so in a room. Help topics: admin".to_owned()
         }
     }
 
     fn on_msg(client: &mut CommandClient, content: &str) {
-        let content = match Self::handle_msg(&content, client.room()) {
-            Ok(Some(resp)) => resp,
-            Ok(None) => return,
-            Err(err) => err.to_string(),
-        };
-        client.respond(content);
+        match Self::handle_msg(&content, client.room()) {
+            // Answers usually contain Markdown.
+            Ok(Some(resp)) => client.respond_markdown(resp),
+            Ok(None) => {}
+            Err(err) => client.respond(err.to_string()),
+        }
     }
 
     fn on_admin(client: &mut CommandClient, cmd: &str) {
@@ -1,13 +1,37 @@
-use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use libcommand::*;
+use libcommand::{
+    impl_command, Attachment, Capability, CommandClient, Manifest, ModuleConfig, TrinityCommand,
+};
 use wit_log as log;
 use wit_sync_request;
 
+/// Whether puns should be sent as images, per the `format` config.
+static IMAGE_FORMAT: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    #[default]
+    Text,
+    Image,
+}
+
+#[derive(serde::Deserialize)]
+struct Config {
+    #[serde(default)]
+    format: Format,
+}
+
+struct Pun {
+    id: String,
+    joke: String,
+}
+
 struct Component;
 
 impl Component {
-    fn get_pun(msg: &str) -> Option<String> {
+    fn get_pun(msg: &str) -> Option<Pun> {
         if !msg.starts_with("!pun") {
             return None;
         }
@@ -25,26 +49,69 @@ impl Component {
 
         #[derive(serde::Deserialize)]
         struct Response {
+            id: String,
             joke: String,
         }
 
         serde_json::from_str::<Response>(&resp.body?)
             .ok()
-            .map(|resp| resp.joke)
+            .map(|resp| Pun {
+                id: resp.id,
+                joke: resp.joke,
+            })
+    }
+
+    fn get_pun_image(pun: &Pun) -> Option<Attachment> {
+        let url = format!("https://icanhazdadjoke.com/j/{}.png", pun.id);
+        let resp = wit_sync_request::Request::get(&url).run_binary().ok()?;
+
+        if resp.status != wit_sync_request::ResponseStatus::Success {
+            log::info!("image request failed with non-success status code");
+            return None;
+        }
+
+        let mimetype = resp.content_type.unwrap_or_else(|| "image/png".to_owned());
+        Some(Attachment::new(
+            &format!("{}.png", pun.id),
+            &mimetype,
+            resp.body?,
+        ))
     }
 }
 
 impl TrinityCommand for Component {
-    fn init(_config: HashMap<String, String>) {
+    fn manifest(_config: &ModuleConfig) -> Manifest {
+        libcommand::manifest!()
+            .command("!pun")
+            .capability(Capability::Log)
+            .capability(Capability::SyncRequest)
+            .config_key("format", "either 'text' (default) or 'image'", false)
+    }
+
+    fn init(config: ModuleConfig) -> Result<(), String> {
         let _ = log::set_boxed_logger(Box::new(log::WitLog::new()));
         log::set_max_level(log::LevelFilter::Trace);
         log::trace!("Called the init() method \\o/");
+
+        let config: Config = config.parse()?;
+        IMAGE_FORMAT.store(matches!(config.format, Format::Image), Ordering::Relaxed);
+        Ok(())
     }
 
     fn on_msg(client: &mut CommandClient, msg: &str) {
-        if let Some(content) = Self::get_pun(msg) {
-            client.respond(content);
+        let Some(pun) = Self::get_pun(msg) else {
+            return;
+        };
+
+        if IMAGE_FORMAT.load(Ordering::Relaxed) {
+            if let Some(image) = Self::get_pun_image(&pun) {
+                client.send_image(image);
+                return;
+            }
+            // Fall back to the text version.
         }
+
+        client.respond(pun.joke);
     }
 
     fn on_help(topic: Option<&str>) -> String {
@@ -1,15 +1,20 @@
-use std::collections::HashMap;
-
-use libcommand::{impl_command, TrinityCommand};
+use libcommand::{impl_command, Capability, Manifest, ModuleConfig, TrinityCommand};
 use wit_log as log;
 
 struct Component;
 
 impl TrinityCommand for Component {
-    fn init(_config: HashMap<String, String>) {
+    fn manifest(_config: &ModuleConfig) -> Manifest {
+        libcommand::manifest!()
+            .capability(Capability::Kv)
+            .capability(Capability::Log)
+    }
+
+    fn init(_config: ModuleConfig) -> Result<(), String> {
         let _ = log::set_boxed_logger(Box::new(log::WitLog::new()));
         log::set_max_level(log::LevelFilter::Trace);
         log::trace!("Called the init() method \\o/");
+        Ok(())
     }
 
     fn on_help(_topic: Option<&str>) -> String {
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use libcommand::{impl_command, CommandClient, TrinityCommand};
+use libcommand::{impl_command, Capability, CommandClient, Manifest, ModuleConfig, TrinityCommand};
 use wit_log as log;
 use wit_sync_request;
 
@@ -13,9 +11,19 @@ struct RoomConfig {
 struct Component;
 
 impl TrinityCommand for Component {
-    fn init(_config: HashMap<String, String>) {
+    fn manifest(_config: &ModuleConfig) -> Manifest {
+        libcommand::manifest!()
+            .command("!sb")
+            .capability(Capability::Kv)
+            .capability(Capability::Log)
+            .capability(Capability::SyncRequest)
+            .capability(Capability::Sys)
+    }
+
+    fn init(_config: ModuleConfig) -> Result<(), String> {
         let _ = log::set_boxed_logger(Box::new(crate::log::WitLog::new()));
         log::set_max_level(log::LevelFilter::Trace);
+        Ok(())
     }
 
     fn on_help(topic: Option<&str>) -> String {
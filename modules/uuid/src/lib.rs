@@ -1,8 +1,14 @@
-use libcommand::{impl_command, CommandClient};
+use libcommand::{impl_command, Capability, CommandClient, Manifest, ModuleConfig};
 
 struct Component;
 
 impl libcommand::TrinityCommand for Component {
+    fn manifest(_config: &ModuleConfig) -> Manifest {
+        libcommand::manifest!()
+            .command("!uuid")
+            .capability(Capability::Sys)
+    }
+
     fn on_help(_topic: Option<&str>) -> String {
         "Simple uuid generator".to_owned()
     }
@@ -0,0 +1,3 @@
+mod history_world;
+
+pub use history_world::trinity::api::history::{last, since, Event, HistoryError};
@@ -22,6 +22,13 @@ pub fn remove<T: serde::Serialize + ?Sized>(key: &T) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn keys<K: for<'a> serde::Deserialize<'a>>() -> anyhow::Result<Vec<K>> {
+    wit::keys()?
+        .iter()
+        .map(|key| serde_json::from_slice(key).context("couldn't deserialize key"))
+        .collect()
+}
+
 pub fn set<T: serde::Serialize + ?Sized, V: serde::Serialize + ?Sized>(
     key: &T,
     val: &V,
@@ -0,0 +1,15 @@
+mod room_info_world;
+
+use room_info_world::trinity::api::room_info as wit;
+
+pub use wit::{info, members, power_levels, Member, Room, RoomInfoError, RoomPowerLevels};
+
+/// Returns the power level of the given user in the current room.
+pub fn power_level(user_id: &str) -> Result<i64, RoomInfoError> {
+    let levels = power_levels()?;
+    Ok(levels
+        .users
+        .into_iter()
+        .find_map(|(user, level)| (user == user_id).then_some(level))
+        .unwrap_or(levels.users_default))
+}
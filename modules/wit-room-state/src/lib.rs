@@ -0,0 +1,5 @@
+mod room_state_world;
+
+pub use room_state_world::trinity::api::room_state::{
+    pin, send_state_event, set_name, set_topic, unpin, RoomStateError,
+};
@@ -67,17 +67,25 @@ impl Request {
     }
 
     pub fn run(self) -> Result<wit::Response, wit::RunRequestError> {
+        wit::run_request(&self.into_wit())
+    }
+
+    /// Runs the request, keeping the response body as raw bytes.
+    pub fn run_binary(self) -> Result<wit::BinaryResponse, wit::RunRequestError> {
+        wit::run_binary_request(&self.into_wit())
+    }
+
+    fn into_wit(self) -> wit::Request {
         let headers: Vec<_> = self
             .headers
             .into_iter()
             .map(|(key, value)| wit::RequestHeader { key, value })
             .collect();
-        let req = wit::Request {
+        wit::Request {
             verb: self.verb,
             url: self.url,
             headers,
             body: self.body,
-        };
-        wit::run_request(&req)
+        }
     }
 }
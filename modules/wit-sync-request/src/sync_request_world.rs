@@ -135,6 +135,25 @@ pub mod trinity {
                         .finish()
                 }
             }
+            /// A response whose body is kept as raw bytes, e.g. for images.
+            #[derive(Clone)]
+            pub struct BinaryResponse {
+                pub status: ResponseStatus,
+                pub content_type: Option<_rt::String>,
+                pub body: Option<_rt::Vec<u8>>,
+            }
+            impl ::core::fmt::Debug for BinaryResponse {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    f.debug_struct("BinaryResponse")
+                        .field("status", &self.status)
+                        .field("content-type", &self.content_type)
+                        .field("body", &self.body)
+                        .finish()
+                }
+            }
             /// An error happened while trying to run a request.
             #[derive(Clone)]
             pub enum RunRequestError {
@@ -377,6 +396,240 @@ pub mod trinity {
                     result24
                 }
             }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn run_binary_request(
+                req: &Request,
+            ) -> Result<BinaryResponse, RunRequestError> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 8 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 8
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let Request {
+                        verb: verb0,
+                        url: url0,
+                        headers: headers0,
+                        body: body0,
+                    } = req;
+                    let vec1 = url0;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let vec5 = headers0;
+                    let len5 = vec5.len();
+                    let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec5.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result5 = if layout5.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout5);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec5.into_iter().enumerate() {
+                        let base = result5
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let RequestHeader { key: key2, value: value2 } = e;
+                            let vec3 = key2;
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = value2;
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
+                            *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    }
+                    let (result7_0, result7_1, result7_2) = match body0 {
+                        Some(e) => {
+                            let vec6 = e;
+                            let ptr6 = vec6.as_ptr().cast::<u8>();
+                            let len6 = vec6.len();
+                            (1i32, ptr6.cast_mut(), len6)
+                        }
+                        None => (0i32, ::core::ptr::null_mut(), 0usize),
+                    };
+                    let ptr8 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "trinity:api/sync-request")]
+                    unsafe extern "C" {
+                        #[link_name = "run-binary-request"]
+                        fn wit_import9(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import9(
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: i32,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import9(
+                            verb0.clone() as i32,
+                            ptr1.cast_mut(),
+                            len1,
+                            result5,
+                            len5,
+                            result7_0,
+                            result7_1,
+                            result7_2,
+                            ptr8,
+                        )
+                    };
+                    let l10 = i32::from(*ptr8.add(0).cast::<u8>());
+                    let result28 = match l10 {
+                        0 => {
+                            let e = {
+                                let l11 = i32::from(
+                                    *ptr8.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                let l12 = i32::from(
+                                    *ptr8
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>(),
+                                );
+                                let l16 = i32::from(
+                                    *ptr8
+                                        .add(5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>(),
+                                );
+                                BinaryResponse {
+                                    status: ResponseStatus::_lift(l11 as u8),
+                                    content_type: match l12 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l13 = *ptr8
+                                                    .add(3 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l14 = *ptr8
+                                                    .add(4 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len15 = l14;
+                                                let bytes15 = _rt::Vec::from_raw_parts(
+                                                    l13.cast(),
+                                                    len15,
+                                                    len15,
+                                                );
+                                                _rt::string_lift(bytes15)
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    },
+                                    body: match l16 {
+                                        0 => None,
+                                        1 => {
+                                            let e = {
+                                                let l17 = *ptr8
+                                                    .add(6 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<*mut u8>();
+                                                let l18 = *ptr8
+                                                    .add(7 * ::core::mem::size_of::<*const u8>())
+                                                    .cast::<usize>();
+                                                let len19 = l18;
+                                                _rt::Vec::from_raw_parts(l17.cast(), len19, len19)
+                                            };
+                                            Some(e)
+                                        }
+                                        _ => _rt::invalid_enum_discriminant(),
+                                    },
+                                }
+                            };
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l20 = i32::from(
+                                    *ptr8.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                let v27 = match l20 {
+                                    0 => {
+                                        let e27 = {
+                                            let l21 = *ptr8
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l22 = *ptr8
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len23 = l22;
+                                            let bytes23 = _rt::Vec::from_raw_parts(
+                                                l21.cast(),
+                                                len23,
+                                                len23,
+                                            );
+                                            _rt::string_lift(bytes23)
+                                        };
+                                        RunRequestError::Builder(e27)
+                                    }
+                                    n => {
+                                        debug_assert_eq!(n, 1, "invalid enum discriminant");
+                                        let e27 = {
+                                            let l24 = *ptr8
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l25 = *ptr8
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len26 = l25;
+                                            let bytes26 = _rt::Vec::from_raw_parts(
+                                                l24.cast(),
+                                                len26,
+                                                len26,
+                                            );
+                                            _rt::string_lift(bytes26)
+                                        };
+                                        RunRequestError::Execute(e27)
+                                    }
+                                };
+                                v27
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    if layout5.size() != 0 {
+                        _rt::alloc::dealloc(result5.cast(), layout5);
+                    }
+                    result28
+                }
+            }
         }
     }
 }
@@ -406,18 +659,20 @@ mod _rt {
 #[unsafe(link_section = "component-type:wit-bindgen:0.41.0:trinity:api:sync-request-world:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 483] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xda\x02\x01A\x02\x01\
-A\x02\x01B\x11\x01m\x04\x03get\x03put\x06delete\x04post\x04\0\x0crequest-verb\x03\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 580] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xbb\x03\x01A\x02\x01\
+A\x02\x01B\x18\x01m\x04\x03get\x03put\x06delete\x04post\x04\0\x0crequest-verb\x03\
 \0\0\x01r\x02\x03keys\x05values\x04\0\x0erequest-header\x03\0\x02\x01p\x03\x01ks\
 \x01r\x04\x04verb\x01\x03urls\x07headers\x04\x04body\x05\x04\0\x07request\x03\0\x06\
 \x01m\x02\x07success\x05error\x04\0\x0fresponse-status\x03\0\x08\x01r\x02\x06sta\
-tus\x09\x04body\x05\x04\0\x08response\x03\0\x0a\x01q\x02\x07builder\x01s\0\x07ex\
-ecute\x01s\0\x04\0\x11run-request-error\x03\0\x0c\x01j\x01\x0b\x01\x0d\x01@\x01\x03\
-req\x07\0\x0e\x04\0\x0brun-request\x01\x0f\x03\0\x18trinity:api/sync-request\x05\
-\0\x04\0\x1etrinity:api/sync-request-world\x04\0\x0b\x18\x01\0\x12sync-request-w\
-orld\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.227.1\
-\x10wit-bindgen-rust\x060.41.0";
+tus\x09\x04body\x05\x04\0\x08response\x03\0\x0a\x01p}\x01k\x0c\x01r\x03\x06statu\
+s\x09\x0ccontent-type\x05\x04body\x0d\x04\0\x0fbinary-response\x03\0\x0e\x01q\x02\
+\x07builder\x01s\0\x07execute\x01s\0\x04\0\x11run-request-error\x03\0\x10\x01j\x01\
+\x0b\x01\x11\x01@\x01\x03req\x07\0\x12\x04\0\x0brun-request\x01\x13\x01j\x01\x0f\
+\x01\x11\x01@\x01\x03req\x07\0\x14\x04\0\x12run-binary-request\x01\x15\x03\0\x18\
+trinity:api/sync-request\x05\0\x04\0\x1etrinity:api/sync-request-world\x04\0\x0b\
+\x18\x01\0\x12sync-request-world\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\
+\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
@@ -0,0 +1,3 @@
+mod typing_world;
+
+pub use typing_world::trinity::api::typing::set_typing;
@@ -0,0 +1,57 @@
+// Generated by `wit-bindgen` 0.41.0. DO NOT EDIT!
+// Options used:
+//   * runtime_path: "wit_bindgen_rt"
+#[rustfmt::skip]
+#[allow(dead_code, clippy::all)]
+pub mod trinity {
+    pub mod api {
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
+        pub mod typing {
+            #[used]
+            #[doc(hidden)]
+            static __FORCE_SECTION_REF: fn() = super::super::super::__link_custom_section_describing_imports;
+            #[allow(unused_unsafe, clippy::all)]
+            /// Shows or hides a typing notice in the room in which the module is handling a message.
+            ///
+            /// The notice expires on its own after a few seconds, so it should be renewed regularly
+            /// during long operations.
+            pub fn set_typing(typing: bool) -> () {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(wasm_import_module = "trinity:api/typing")]
+                    unsafe extern "C" {
+                        #[link_name = "set-typing"]
+                        fn wit_import0(_: i32);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import0(_: i32) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import0(
+                            match &typing {
+                                true => 1,
+                                false => 0,
+                            },
+                        )
+                    };
+                }
+            }
+        }
+    }
+}
+#[cfg(target_arch = "wasm32")]
+#[unsafe(link_section = "component-type:wit-bindgen:0.41.0:trinity:api:typing-world:encoded world")]
+#[doc(hidden)]
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 219] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07Y\x01A\x02\x01A\x02\x01\
+B\x02\x01@\x01\x06typing\x7f\x01\0\x04\0\x0aset-typing\x01\0\x03\0\x12trinity:ap\
+i/typing\x05\0\x04\0\x18trinity:api/typing-world\x04\0\x0b\x12\x01\0\x0ctyping-w\
+orld\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.227.1\
+\x10wit-bindgen-rust\x060.41.0";
+#[inline(never)]
+#[doc(hidden)]
+pub fn __link_custom_section_describing_imports() {
+    wit_bindgen_rt::maybe_link_cabi_realloc();
+}
@@ -8,9 +8,15 @@ const ADMIN_TABLE: redb::TableDefinition<str, [u8]> = redb::TableDefinition::new
 /// Key for the `device_id` value in the admin table.
 pub const DEVICE_ID_ENTRY: &str = "device_id";
 
+/// Key for the persisted session in the admin table.
+pub const SESSION_ENTRY: &str = "session";
+
 /// Key for the `version` value in the admin table.
 pub const VERSION_ENTRY: &str = "version";
 
+/// Prefix for the keys of the per-module data versions in the admin table.
+pub const MODULE_VERSION_PREFIX: &str = "module_version:";
+
 /// Reads a given key in the admin table from the database.
 ///
 /// Returns `Ok(None)` if the value wasn't present, `Ok(Some)` if it did exist.
@@ -59,14 +65,18 @@ pub fn read_u64(db: &ShareableDatabase, key: &str) -> anyhow::Result<Option<u64>
 /// Writes a given key in the admin table from the database.
 pub fn write(db: &ShareableDatabase, key: &str, value: &[u8]) -> anyhow::Result<()> {
     let txn = db.begin_write()?;
-    {
-        let mut table = txn.open_table(ADMIN_TABLE)?;
-        table.insert(key, value)?;
-    }
+    write_in(&txn, key, value)?;
     txn.commit()?;
     Ok(())
 }
 
+/// Same as [`write`], but as part of an existing write transaction.
+pub fn write_in(txn: &redb::WriteTransaction<'_>, key: &str, value: &[u8]) -> anyhow::Result<()> {
+    let mut table = txn.open_table(ADMIN_TABLE)?;
+    table.insert(key, value)?;
+    Ok(())
+}
+
 /// Same as [`write`], but for a string ref.
 pub fn write_str(db: &ShareableDatabase, key: &str, value: &str) -> anyhow::Result<()> {
     write(db, key, value.as_bytes())
@@ -77,12 +87,21 @@ pub fn write_u64(db: &ShareableDatabase, key: &str, value: u64) -> anyhow::Resul
     write(db, key, &value.to_le_bytes())
 }
 
+/// Same as [`write_u64`], but as part of an existing write transaction.
+pub fn write_u64_in(txn: &redb::WriteTransaction<'_>, key: &str, value: u64) -> anyhow::Result<()> {
+    write_in(txn, key, &value.to_le_bytes())
+}
+
 pub fn remove(db: &ShareableDatabase, key: &str) -> anyhow::Result<()> {
     let txn = db.begin_write()?;
-    {
-        let mut table = txn.open_table(ADMIN_TABLE)?;
-        table.remove(key)?;
-    }
+    remove_in(&txn, key)?;
     txn.commit()?;
     Ok(())
 }
+
+/// Same as [`remove`], but as part of an existing write transaction.
+pub fn remove_in(txn: &redb::WriteTransaction<'_>, key: &str) -> anyhow::Result<()> {
+    let mut table = txn.open_table(ADMIN_TABLE)?;
+    table.remove(key)?;
+    Ok(())
+}
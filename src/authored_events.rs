@@ -0,0 +1,155 @@
+//! Remembers which module sent which event, so that replies to an event can be routed back to
+//! the module that sent it, and which events were sent in response to a message, so that they
+//! can be updated when the message is edited.
+//!
+//! Entries are only kept for [`RETENTION`], so that the tables don't grow forever; replies to and
+//! edits of older messages go through the usual dispatch.
+
+use std::time::{Duration, SystemTime};
+
+use redb::ReadableTable as _;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tracing::debug;
+
+use crate::ShareableDatabase;
+
+/// Maps an event ID to the name of the module that sent it.
+const AUTHORED_EVENTS_TABLE: redb::TableDefinition<str, [u8]> =
+    redb::TableDefinition::new("@authored_events");
+
+/// Maps the event ID of a message to the response a module sent to it.
+const RESPONSES_TABLE: redb::TableDefinition<str, [u8]> = redb::TableDefinition::new("@responses");
+
+/// How long entries are kept for.
+const RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// The messages a module sent in response to another message.
+#[derive(Serialize, Deserialize)]
+pub struct Response {
+    /// Name of the module which responded.
+    pub module: String,
+    /// Event IDs of the messages sent in response, in order.
+    pub events: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Author {
+    module: String,
+}
+
+/// An entry of one of the tables, along with the time it was recorded at.
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    /// Seconds since the Unix epoch.
+    recorded_at: u64,
+    #[serde(flatten)]
+    value: T,
+}
+
+/// Records that the given event has been sent on behalf of the given module.
+pub fn record(db: &ShareableDatabase, event_id: &str, module: &str) -> anyhow::Result<()> {
+    let author = Author {
+        module: module.to_owned(),
+    };
+    insert(db, AUTHORED_EVENTS_TABLE, event_id, author)
+}
+
+/// Returns the name of the module that sent the given event, if it was sent by a module.
+pub fn author(db: &ShareableDatabase, event_id: &str) -> anyhow::Result<Option<String>> {
+    Ok(get::<Author>(db, AUTHORED_EVENTS_TABLE, event_id)?.map(|author| author.module))
+}
+
+/// Records the response sent to the given message.
+pub fn record_response(
+    db: &ShareableDatabase,
+    event_id: &str,
+    response: &Response,
+) -> anyhow::Result<()> {
+    insert(db, RESPONSES_TABLE, event_id, response)
+}
+
+/// Returns the response sent to the given message, if a module responded to it.
+pub fn response(db: &ShareableDatabase, event_id: &str) -> anyhow::Result<Option<Response>> {
+    get(db, RESPONSES_TABLE, event_id)
+}
+
+/// Removes the entries older than [`RETENTION`], along with the ones that can't be read.
+pub fn prune(db: &ShareableDatabase) -> anyhow::Result<()> {
+    /// Only the timestamp of an entry, for any kind of entry.
+    #[derive(Deserialize)]
+    struct Timestamp {
+        recorded_at: u64,
+    }
+
+    let oldest = now().saturating_sub(RETENTION.as_secs());
+
+    let txn = db.begin_write()?;
+    for (name, definition) in [
+        ("authored events", AUTHORED_EVENTS_TABLE),
+        ("responses", RESPONSES_TABLE),
+    ] {
+        let mut table = txn.open_table(definition)?;
+        let expired: Vec<String> = table
+            .range::<std::ops::RangeFull, &str>(..)?
+            .filter(|(_, entry)| {
+                serde_json::from_slice::<Timestamp>(entry)
+                    .map_or(true, |entry| entry.recorded_at < oldest)
+            })
+            .map(|(event_id, _)| event_id.to_owned())
+            .collect();
+
+        for event_id in &expired {
+            table.remove(event_id)?;
+        }
+        if !expired.is_empty() {
+            debug!("pruned {} old {name}", expired.len());
+        }
+    }
+    txn.commit()?;
+
+    Ok(())
+}
+
+fn insert<T: Serialize>(
+    db: &ShareableDatabase,
+    definition: redb::TableDefinition<str, [u8]>,
+    event_id: &str,
+    value: T,
+) -> anyhow::Result<()> {
+    let entry = serde_json::to_vec(&Entry {
+        recorded_at: now(),
+        value,
+    })?;
+    let txn = db.begin_write()?;
+    {
+        let mut table = txn.open_table(definition)?;
+        table.insert(event_id, &entry)?;
+    }
+    txn.commit()?;
+    Ok(())
+}
+
+fn get<T: DeserializeOwned>(
+    db: &ShareableDatabase,
+    definition: redb::TableDefinition<str, [u8]>,
+    event_id: &str,
+) -> anyhow::Result<Option<T>> {
+    let txn = db.begin_read()?;
+    let table = match txn.open_table(definition) {
+        Ok(table) => table,
+        Err(redb::Error::TableDoesNotExist(_)) => return Ok(None),
+        Err(err) => Err(err)?,
+    };
+    match table.get(event_id)? {
+        Some(entry) => Ok(Some(serde_json::from_slice::<Entry<T>>(entry)?.value)),
+        None => Ok(None),
+    }
+}
+
+/// Current time, in seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -1,4 +1,3 @@
-use anyhow;
 use trinity::BotConfig;
 
 async fn real_main() -> anyhow::Result<()> {
@@ -6,8 +5,19 @@ async fn real_main() -> anyhow::Result<()> {
     let config_path = std::env::args().nth(1);
 
     tracing::debug!("parsing config...");
-    // First check for a config file, then fallback to env if none found.
-    let config = BotConfig::from_config(config_path).or_else(|_| BotConfig::from_env())?;
+    // First check for a config file, then fallback to env if none found; errors in an existing
+    // config file are reported.
+    let config = match BotConfig::from_config(config_path) {
+        Ok(config) => config,
+        Err(err)
+            if err
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|err| err.kind() == std::io::ErrorKind::NotFound) =>
+        {
+            BotConfig::from_env()?
+        }
+        Err(err) => return Err(err.context("invalid config file")),
+    };
 
     tracing::debug!("creating client...");
     trinity::run(config).await
@@ -0,0 +1,142 @@
+//! Expansion of references to environment variables and files in the configuration.
+//!
+//! The strings of the credentials and of the modules' configuration can refer to an environment
+//! variable with `${NAME}`, or to the contents of a file with `${file:/path/to/file}`, e.g. for
+//! secrets mounted by Kubernetes or systemd. `$${` is kept as a literal `${`. Other values are
+//! left as is, so that e.g. paths don't need escaping.
+
+use std::{env, fs, path::Path};
+
+use anyhow::Context as _;
+
+/// Top-level keys of the configuration whose strings are expanded.
+const EXPANDED_KEYS: &[&str] = &["password", "access_token", "modules_config"];
+
+/// Expands the references in the strings of the credentials and of the modules' configuration.
+pub(crate) fn expand_secrets(config: &mut toml::Value) -> anyhow::Result<()> {
+    let Some(table) = config.as_table_mut() else {
+        return Ok(());
+    };
+    for key in EXPANDED_KEYS {
+        if let Some(value) = table.get_mut(*key) {
+            expand(value, key)?;
+        }
+    }
+    Ok(())
+}
+
+/// Expands the references in all the strings of a configuration value.
+///
+/// `key` is the dotted path to the value, used in error messages.
+fn expand(value: &mut toml::Value, key: &str) -> anyhow::Result<()> {
+    match value {
+        toml::Value::String(string) => {
+            *string = expand_str(string).with_context(|| format!("invalid value for `{key}`"))?;
+        }
+        toml::Value::Array(values) => {
+            for (index, value) in values.iter_mut().enumerate() {
+                expand(value, &format!("{key}[{index}]"))?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (name, value) in table.iter_mut() {
+                let key = if key.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{key}.{name}")
+                };
+                expand(value, &key)?;
+            }
+        }
+        toml::Value::Integer(_)
+        | toml::Value::Float(_)
+        | toml::Value::Boolean(_)
+        | toml::Value::Datetime(_) => {}
+    }
+    Ok(())
+}
+
+fn expand_str(string: &str) -> anyhow::Result<String> {
+    let mut result = String::with_capacity(string.len());
+    let mut rest = string;
+
+    while let Some(start) = rest.find("${") {
+        // `$${` is an escaped `${`.
+        if rest[..start].ends_with('$') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .with_context(|| format!("unterminated reference in {string:?}"))?;
+        let reference = &rest[start + 2..start + end];
+
+        let expanded = match reference.strip_prefix("file:") {
+            Some(path) => read_secret(Path::new(path))?,
+            None => env::var(reference)
+                .with_context(|| format!("environment variable {reference} isn't set"))?,
+        };
+        result.push_str(&expanded);
+        rest = &rest[start + end + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Reads a secret from a file, ignoring the trailing newline if any.
+pub(crate) fn read_secret(path: &Path) -> anyhow::Result<String> {
+    // The I/O error isn't kept as the source, so that a missing secret isn't mistaken for a
+    // missing config file.
+    let contents = fs::read_to_string(path)
+        .map_err(|err| anyhow::anyhow!("couldn't read secret file {}: {err}", path.display()))?;
+    Ok(contents.trim_end_matches(['\n', '\r']).to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_escaped_references() {
+        assert_eq!(expand_str("no reference").unwrap(), "no reference");
+        assert_eq!(expand_str("pa$${ss}word").unwrap(), "pa${ss}word");
+        assert_eq!(expand_str("$${").unwrap(), "${");
+    }
+
+    #[test]
+    fn expand_env_vars() {
+        // Cargo sets this variable when running the tests.
+        assert_eq!(
+            expand_str("name: ${CARGO_PKG_NAME}!").unwrap(),
+            format!("name: {}!", env!("CARGO_PKG_NAME"))
+        );
+        let err = expand_str("${TRINITY_UNSET_TEST_VARIABLE}").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("TRINITY_UNSET_TEST_VARIABLE isn't set")
+        );
+    }
+
+    #[test]
+    fn expand_file_references() {
+        let path = env::temp_dir().join(format!("trinity-secret-{}", std::process::id()));
+        fs::write(&path, "s3cret\n").unwrap();
+        let expanded = expand_str(&format!("${{file:{}}}", path.display()));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(expanded.unwrap(), "s3cret");
+
+        let err = expand_str("${file:/nonexistent/trinity-secret}").unwrap_err();
+        assert!(err.to_string().contains("couldn't read secret file"));
+    }
+
+    #[test]
+    fn expand_unterminated_references() {
+        let err = expand_str("pass${word").unwrap_err();
+        assert!(err.to_string().contains("unterminated reference"));
+    }
+}
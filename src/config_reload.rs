@@ -0,0 +1,194 @@
+//! Live reload of the configuration file.
+//!
+//! The config file is reloaded when it changes, or when the bot receives SIGHUP. The module
+//! specific configuration and permissions, the modules paths and the admin are applied without
+//! restarting; other settings only take effect after a restart.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use notify::{RecursiveMode, Watcher as _};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tracing::{debug, error, info, warn};
+
+use crate::{App, BotConfig, notify_admin, report_load_failures};
+
+/// Delay before reloading the config after a change, so that all the writes to the file are done.
+const RELOAD_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Watches the config file, asking for a reload when it changes.
+///
+/// The parent directory is watched rather than the file itself, since editors often replace the
+/// file instead of writing to it.
+pub(crate) fn watch(
+    config_path: &Path,
+    reload: UnboundedSender<()>,
+) -> anyhow::Result<notify::RecommendedWatcher> {
+    let file_name = config_path.file_name().map(ToOwned::to_owned);
+    let dir = match config_path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut watcher =
+        notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| match res {
+            Ok(event) => {
+                if !matches!(
+                    event.kind,
+                    notify::EventKind::Create(_) | notify::EventKind::Modify(_)
+                ) {
+                    return;
+                }
+                if event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name() == file_name.as_deref())
+                {
+                    let _ = reload.send(());
+                }
+            }
+            Err(e) => warn!("config watch error: {e:?}"),
+        })?;
+
+    debug!(
+        "setting up watcher on config file @ {}...",
+        config_path.display()
+    );
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+/// Reloads the config each time it's asked to, until all the senders are dropped.
+///
+/// `modules_watcher` is updated when modules paths are added or removed.
+pub(crate) async fn run(
+    app: App,
+    config_path: Option<PathBuf>,
+    mut modules_watcher: notify::RecommendedWatcher,
+    mut requests: UnboundedReceiver<()>,
+) {
+    while requests.recv().await.is_some() {
+        tokio::time::sleep(RELOAD_DELAY).await;
+        // Batch the requests received in the meanwhile.
+        while requests.try_recv().is_ok() {}
+
+        let Some(config_path) = &config_path else {
+            warn!("not reloading the config, since it hasn't been read from a file");
+            continue;
+        };
+
+        reload(&app, config_path, &mut modules_watcher).await;
+    }
+}
+
+/// Reads the config file again, and applies the changes if it's valid.
+async fn reload(app: &App, config_path: &Path, modules_watcher: &mut notify::RecommendedWatcher) {
+    let config = read(config_path);
+
+    let mut ctx = app.inner.lock().await;
+
+    let config = match config {
+        Ok(config) => config,
+        Err(err) => {
+            error!("invalid config, keeping the previous one: {err:#}");
+            notify_admin(
+                &ctx.outbox,
+                &ctx.client,
+                &ctx.admin_user_id,
+                format!("The config couldn't be reloaded, keeping the previous one: {err:#}"),
+            );
+            return;
+        }
+    };
+
+    if ctx.admin_user_id != config.admin_user_id {
+        info!("admin is now {}", config.admin_user_id);
+        ctx.admin_user_id = config.admin_user_id;
+    }
+
+    for path in &ctx.modules_paths {
+        if !config.modules_paths.contains(path) {
+            debug!("removing watcher on @ {}...", path.to_string_lossy());
+            if let Err(err) = modules_watcher.unwatch(path) {
+                warn!("couldn't stop watching {}: {err}", path.to_string_lossy());
+            }
+        }
+    }
+    for path in &config.modules_paths {
+        if !ctx.modules_paths.contains(path) {
+            debug!("setting up watcher on @ {}...", path.to_string_lossy());
+            if let Err(err) = modules_watcher.watch(path, RecursiveMode::Recursive) {
+                error!("couldn't watch {}: {err}", path.to_string_lossy());
+            }
+        }
+    }
+
+    let modules_config = config.modules_config.unwrap_or_default();
+    let modules_permissions = config.modules_permissions.unwrap_or_default();
+
+    // Modules are reinitialized when their config or their permissions changed.
+    let mut reconfigured = changed_entries(&ctx.modules_config, &modules_config);
+    reconfigured.extend(changed_entries(&ctx.host.permissions, &modules_permissions));
+
+    let paths_changed = ctx.modules_paths != config.modules_paths;
+    ctx.modules_paths = config.modules_paths;
+    ctx.modules_config = modules_config;
+    ctx.host.permissions = modules_permissions;
+
+    if paths_changed || !reconfigured.is_empty() {
+        drop(ctx);
+
+        // Loading the modules is slow and blocking, so it's done outside of the async runtime.
+        let inner = app.inner.clone();
+        let reconfigure = tokio::task::spawn_blocking(move || {
+            let ctx = &mut *futures::executor::block_on(inner.lock());
+            ctx.modules.reconfigure(
+                &ctx.engine,
+                &ctx.host,
+                &ctx.modules_paths,
+                &ctx.modules_config,
+                &reconfigured,
+            )
+        });
+        match reconfigure.await {
+            Ok(failures) => {
+                let ctx = app.inner.lock().await;
+                report_load_failures(&ctx.outbox, &ctx.client, &ctx.admin_user_id, failures);
+            }
+            Err(err) => error!("couldn't reconfigure the modules: {err}"),
+        }
+    }
+
+    info!("config reloaded");
+}
+
+/// Reads and validates the config file.
+fn read(config_path: &Path) -> anyhow::Result<BotConfig> {
+    let config = BotConfig::from_config(Some(config_path.to_string_lossy().into_owned()))?;
+
+    // Make sure all the modules paths exist, so that a typo doesn't unload all the modules.
+    for path in &config.modules_paths {
+        anyhow::ensure!(
+            path.exists(),
+            "{} doesn't reference a valid path",
+            path.to_string_lossy()
+        );
+    }
+
+    Ok(config)
+}
+
+/// Names of the entries that are different in the two maps, including the ones missing in one of
+/// them.
+fn changed_entries<V: PartialEq>(
+    old: &HashMap<String, V>,
+    new: &HashMap<String, V>,
+) -> HashSet<String> {
+    old.keys()
+        .chain(new.keys())
+        .filter(|name| old.get(*name) != new.get(*name))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,317 @@
+//! Helpers to produce HTML that's safe to send in Matrix messages.
+
+use std::{borrow::Cow, collections::HashSet, sync::LazyLock};
+
+use matrix_sdk::ruma::OwnedUserId;
+
+use crate::wasm;
+
+/// A user mentioned in a message, with their display name if they have one.
+pub(crate) type Mentioned = (OwnedUserId, Option<String>);
+
+/// Tags allowed in the `formatted_body` of a message, as listed in the Matrix specification.
+const ALLOWED_TAGS: &[&str] = &[
+    "font",
+    "del",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "blockquote",
+    "p",
+    "a",
+    "ul",
+    "ol",
+    "sup",
+    "sub",
+    "li",
+    "b",
+    "i",
+    "u",
+    "strong",
+    "em",
+    "strike",
+    "s",
+    "code",
+    "hr",
+    "br",
+    "div",
+    "table",
+    "thead",
+    "tbody",
+    "tr",
+    "th",
+    "td",
+    "caption",
+    "pre",
+    "span",
+    "img",
+    "details",
+    "summary",
+];
+
+/// Attributes allowed per tag, as listed in the Matrix specification.
+const ALLOWED_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("font", &["data-mx-bg-color", "data-mx-color", "color"]),
+    (
+        "span",
+        &["data-mx-bg-color", "data-mx-color", "data-mx-spoiler"],
+    ),
+    ("a", &["name", "target", "href"]),
+    ("img", &["width", "height", "alt", "title", "src"]),
+    ("ol", &["start"]),
+    ("code", &["class"]),
+];
+
+const ALLOWED_URL_SCHEMES: &[&str] = &["https", "http", "ftp", "mailto", "magnet", "mxc"];
+
+static SANITIZER: LazyLock<ammonia::Builder<'static>> = LazyLock::new(|| {
+    let mut builder = ammonia::Builder::empty();
+    builder
+        .add_tags(ALLOWED_TAGS)
+        .url_schemes(HashSet::from_iter(ALLOWED_URL_SCHEMES.iter().copied()))
+        .url_relative(ammonia::UrlRelative::Deny)
+        .link_rel(None)
+        .attribute_filter(filter_attribute);
+    for (tag, attributes) in ALLOWED_ATTRIBUTES {
+        builder.add_tag_attributes(tag, *attributes);
+    }
+    builder
+});
+
+/// Refines the per-tag allow list: images can only point to Matrix content, and code blocks
+/// can only have a language class.
+fn filter_attribute<'u>(element: &str, attribute: &str, value: &'u str) -> Option<Cow<'u, str>> {
+    match (element, attribute) {
+        ("img", "src") if !value.starts_with("mxc://") => None,
+        ("code", "class") if !value.starts_with("language-") => None,
+        _ => Some(value.into()),
+    }
+}
+
+/// Removes all the tags and attributes that aren't allowed in Matrix messages.
+pub(crate) fn sanitize(html: &str) -> String {
+    SANITIZER.clean(html).to_string()
+}
+
+/// Renders CommonMark into sanitized HTML.
+pub(crate) fn render_markdown(markdown: &str) -> String {
+    let parser = pulldown_cmark::Parser::new_ext(
+        markdown,
+        pulldown_cmark::Options::ENABLE_TABLES | pulldown_cmark::Options::ENABLE_STRIKETHROUGH,
+    );
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, parser);
+    sanitize(&html)
+}
+
+/// Renders CommonMark into sanitized HTML, without the enclosing paragraph if the input is a
+/// single paragraph, so the result can be embedded in a line of text.
+pub(crate) fn render_markdown_inline(markdown: &str) -> String {
+    let html = render_markdown(markdown);
+    let trimmed = html.trim_end();
+    match trimmed
+        .strip_prefix("<p>")
+        .and_then(|html| html.strip_suffix("</p>"))
+    {
+        Some(inner) if !inner.contains("<p>") => inner.to_owned(),
+        _ => html,
+    }
+}
+
+/// Escapes text so it can be embedded as is in HTML.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders a message to the text and the HTML (if any) it's sent with, the mentioned users being
+/// shown with their names and as pills.
+pub(crate) fn render_message(
+    msg: &wasm::Message,
+    mentioned: &[Mentioned],
+) -> (String, Option<String>) {
+    let html = match &msg.format {
+        wasm::MessageFormat::Plain => {
+            (!mentioned.is_empty()).then(|| escape(&msg.text).replace('\n', "<br>"))
+        }
+        wasm::MessageFormat::Markdown => Some(render_markdown(&msg.text)),
+        wasm::MessageFormat::Html(html) => Some(sanitize(html)),
+    };
+    let html = html.map(|mut html| {
+        for (user_id, name) in mentioned {
+            let name = name.as_deref().unwrap_or(user_id.as_str());
+            html = add_pill(&html, user_id.as_str(), name);
+        }
+        html
+    });
+
+    let mut text = msg.text.clone();
+    for (user_id, name) in mentioned {
+        if let Some(name) = name {
+            let mut replaced = String::with_capacity(text.len());
+            replace_words(&text, user_id.as_str(), name, &mut replaced);
+            text = replaced;
+        }
+    }
+
+    (text, html)
+}
+
+/// Replaces the occurrences of a user ID in some sanitized HTML with a link to the user (a
+/// "pill"), showing the given name.
+///
+/// Only whole words of the text are replaced, outside of links and code; tags and attributes are
+/// left as is. The HTML is left untouched if it already links to the user.
+pub(crate) fn add_pill(html: &str, user_id: &str, name: &str) -> String {
+    let link = format!("https://matrix.to/#/{user_id}");
+    if html.contains(&link) {
+        return html.to_owned();
+    }
+    let user_id = escape(user_id);
+    let pill = format!("<a href=\"{link}\">{}</a>", escape(name));
+
+    let mut result = String::with_capacity(html.len());
+    // How many links and code elements the current text is in.
+    let mut excluded = 0usize;
+    let mut rest = html;
+    while !rest.is_empty() {
+        let end = if rest.starts_with('<') {
+            let end = tag_end(rest);
+            let tag = &rest[1..end];
+            let (closing, tag) = match tag.strip_prefix('/') {
+                Some(tag) => (true, tag),
+                None => (false, tag),
+            };
+            let tag_name: String = tag
+                .chars()
+                .take_while(char::is_ascii_alphanumeric)
+                .collect();
+            if matches!(tag_name.as_str(), "a" | "code" | "pre") {
+                excluded = if closing {
+                    excluded.saturating_sub(1)
+                } else {
+                    excluded + 1
+                };
+            }
+            result.push_str(&rest[..end]);
+            end
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..end];
+            if excluded == 0 {
+                replace_words(text, &user_id, &pill, &mut result);
+            } else {
+                result.push_str(text);
+            }
+            end
+        };
+        rest = &rest[end..];
+    }
+    result
+}
+
+/// Position right after the end of the tag starting the given HTML, ignoring the `>` in quoted
+/// attribute values.
+fn tag_end(html: &str) -> usize {
+    let mut in_quotes = false;
+    for (i, c) in html.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '>' if !in_quotes => return i + 1,
+            _ => {}
+        }
+    }
+    html.len()
+}
+
+/// Appends the text to `result`, replacing the occurrences of `word` that aren't part of a longer
+/// word (e.g. a longer user ID) with `replacement`.
+fn replace_words(text: &str, word: &str, replacement: &str, result: &mut String) {
+    // Characters that can be part of a user ID; a trailing `.` or `:` is punctuation, though.
+    let is_id_char = |c: char| c.is_alphanumeric() || "-_.=/+:".contains(c);
+
+    let mut last = 0;
+    for (start, _) in text.match_indices(word) {
+        let end = start + word.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].trim_start_matches(['.', ':']).chars().next();
+        if start < last || before.is_some_and(is_id_char) || after.is_some_and(is_id_char) {
+            continue;
+        }
+        result.push_str(&text[last..start]);
+        result.push_str(replacement);
+        last = end;
+    }
+    result.push_str(&text[last..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replaced(text: &str) -> String {
+        let mut result = String::new();
+        replace_words(text, "@bot:example.org", "Bot", &mut result);
+        result
+    }
+
+    #[test]
+    fn replace_whole_words() {
+        assert_eq!(replaced("@bot:example.org"), "Bot");
+        assert_eq!(replaced("hi @bot:example.org!"), "hi Bot!");
+        assert_eq!(replaced("@bot:example.org, @bot:example.org."), "Bot, Bot.");
+        assert_eq!(replaced("@bot:example.org: hello"), "Bot: hello");
+    }
+
+    #[test]
+    fn replace_words_skips_longer_ids() {
+        assert_eq!(replaced("@bot:example.org.uk"), "@bot:example.org.uk");
+        assert_eq!(replaced("@bot:example.organic"), "@bot:example.organic");
+        assert_eq!(replaced("x@bot:example.org"), "x@bot:example.org");
+        assert_eq!(replaced("@bot:example.org:8448"), "@bot:example.org:8448");
+    }
+
+    #[test]
+    fn tag_end_skips_quoted_attributes() {
+        assert_eq!(tag_end("<br>text"), 4);
+        assert_eq!(tag_end(r#"<a href="x>y">text</a>"#), 14);
+        assert_eq!(tag_end("<unterminated"), 13);
+    }
+
+    #[test]
+    fn add_pill_to_text() {
+        assert_eq!(
+            add_pill("hi @bot:example.org<br>", "@bot:example.org", "<Bot>"),
+            r#"hi <a href="https://matrix.to/#/@bot:example.org">&lt;Bot&gt;</a><br>"#
+        );
+    }
+
+    #[test]
+    fn add_pill_skips_tags_links_and_code() {
+        let html = concat!(
+            r#"<a href="https://example.org/@bot:example.org">@bot:example.org</a> "#,
+            "<code>@bot:example.org</code> ",
+            "<pre><code>@bot:example.org</code></pre>",
+        );
+        assert_eq!(add_pill(html, "@bot:example.org", "Bot"), html);
+    }
+
+    #[test]
+    fn add_pill_keeps_existing_pills() {
+        let html = r#"<a href="https://matrix.to/#/@bot:example.org">Bot</a> @bot:example.org"#;
+        assert_eq!(add_pill(html, "@bot:example.org", "Bot"), html);
+    }
+}
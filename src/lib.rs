@@ -1,23 +1,40 @@
 mod admin_table;
+mod authored_events;
+mod config;
+mod config_reload;
+mod html;
+mod login;
+mod long_messages;
+mod loop_protection;
+mod migrations;
+mod outbox;
 mod room_resolver;
 mod wasm;
 
 use anyhow::Context;
+use login::OAuthConfig;
+use long_messages::LongMessages;
+use loop_protection::{LoopProtection, LoopProtectionConfig};
 use matrix_sdk::{
     Client, LoopCtrl, RoomState,
     config::SyncSettings,
     event_handler::Ctx,
     room::Room,
     ruma::{
-        OwnedUserId, RoomId, UserId,
+        EventId, OwnedEventId, OwnedUserId, RoomId, UInt, UserId,
+        api::client::error::ErrorKind,
         events::{
-            AnyMessageLikeEventContent,
+            Mentions,
             reaction::ReactionEventContent,
             relation::{Annotation, Thread},
             room::{
+                ImageInfo, MediaSource, ThumbnailInfo,
                 member::StrippedRoomMemberEvent,
                 message::{
-                    MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
+                    AudioInfo, AudioMessageEventContent, FileInfo, FileMessageEventContent,
+                    ImageMessageEventContent, MessageType, OriginalSyncRoomMessageEvent, Relation,
+                    ReplacementMetadata, RoomMessageEventContent, VideoInfo,
+                    VideoMessageEventContent,
                 },
                 tombstone::OriginalSyncRoomTombstoneEvent,
             },
@@ -26,17 +43,21 @@ use matrix_sdk::{
     },
 };
 use notify::{RecursiveMode, Watcher};
+use outbox::Outbox;
 use room_resolver::RoomResolver;
 use serde::Deserialize;
-use std::{collections::HashMap, env, fs, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::PathBuf,
+    sync::Arc,
+};
 use tokio::{
-    sync::Mutex,
-    time::{Duration, sleep},
+    sync::{Mutex, mpsc::UnboundedSender},
+    time::{Duration, Instant, sleep},
 };
 use tracing::{debug, error, info, trace, warn};
-use wasm::{Module, WasmModules};
-
-use crate::admin_table::DEVICE_ID_ENTRY;
+use wasm::{HostContext, LoadFailure, Module, Permission, WasmModules};
 
 /// The configuration to run a trinity instance with.
 #[derive(Deserialize)]
@@ -46,7 +67,15 @@ pub struct BotConfig {
     /// the user_id to be used on the homeserver.
     pub user_id: String,
     /// password to be used to log into the homeserver.
-    pub password: String,
+    pub password: Option<String>,
+    /// file containing the password, e.g. a mounted secret; exclusive with `password`.
+    pub password_file: Option<PathBuf>,
+    /// pre-issued access token to use instead of logging in; requires `device_id`.
+    pub access_token: Option<String>,
+    /// device ID matching the `access_token`.
+    pub device_id: Option<String>,
+    /// log in through OAuth instead of using a password.
+    pub oauth: Option<OAuthConfig>,
     /// where to store the matrix-sdk internal data.
     pub matrix_store_path: String,
     /// where to store the additional database data.
@@ -55,8 +84,24 @@ pub struct BotConfig {
     pub admin_user_id: OwnedUserId,
     /// paths where modules can be loaded.
     pub modules_paths: Vec<PathBuf>,
-    /// module specific configuration to forward to corresponding handler.
-    pub modules_config: Option<HashMap<String, HashMap<String, String>>>,
+    /// module specific configuration to forward to corresponding handler; values can be nested
+    /// tables and arrays.
+    pub modules_config: Option<HashMap<String, serde_json::Value>>,
+    /// additional permissions granted to modules, by module name.
+    pub modules_permissions: Option<HashMap<String, Vec<Permission>>>,
+    /// maximum length of a message sent by the bot, in bytes.
+    pub max_message_length: Option<usize>,
+    /// what to do with the responses that are too long, by module name; they're split by default.
+    pub modules_long_messages: Option<HashMap<String, long_messages::Strategy>>,
+    /// which messages to ignore, and when to stop responding, to avoid loops with other bots.
+    #[serde(default)]
+    pub loop_protection: LoopProtectionConfig,
+    /// only log the data migrations that would run, then exit.
+    #[serde(default)]
+    pub migrations_dry_run: bool,
+    /// the file this configuration has been read from, if any; it's reloaded when it changes.
+    #[serde(skip)]
+    pub config_path: Option<PathBuf>,
 }
 
 impl BotConfig {
@@ -68,19 +113,47 @@ impl BotConfig {
         let config_path = match path {
             Some(a) => a,
             None => {
-                let dirs = directories::ProjectDirs::from("", "", "trinity")
-                    .context("config file not found")?;
+                let dirs = directories::ProjectDirs::from("", "", "trinity").ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, "config file not found")
+                })?;
                 let path = dirs.config_dir().join("config.toml");
                 String::from(path.to_str().unwrap())
             }
         };
         let contents = fs::read_to_string(&config_path)?;
-        let config: BotConfig = toml::from_str(&contents)?;
+
+        let mut value: toml::Value = toml::from_str(&contents)?;
+        config::expand_secrets(&mut value)?;
+        let mut config: BotConfig = match value.try_into() {
+            Ok(config) => config,
+            // Errors for the original file name the offending key, unlike the ones for the
+            // expanded value, so prefer them.
+            Err(err) => {
+                let err = toml::from_str::<BotConfig>(&contents).err().unwrap_or(err);
+                return Err(err.into());
+            }
+        };
+        config.read_password_file()?;
 
         debug!("Using configuration from {config_path}");
+        config.config_path = Some(PathBuf::from(config_path));
         Ok(config)
     }
 
+    /// Reads the password from `password_file`, if set.
+    fn read_password_file(&mut self) -> anyhow::Result<()> {
+        let Some(path) = &self.password_file else {
+            return Ok(());
+        };
+        anyhow::ensure!(
+            self.password.is_none(),
+            "only one of `password` and `password_file` can be set"
+        );
+        self.password =
+            Some(config::read_secret(path).context("invalid value for `password_file`")?);
+        Ok(())
+    }
+
     /// Generate a `BotConfig` from the process' environment.
     pub fn from_env() -> anyhow::Result<Self> {
         // override environment variables with contents of .env file, unless they were already set
@@ -89,7 +162,10 @@ impl BotConfig {
 
         let home_server = env::var("HOMESERVER").context("missing HOMESERVER variable")?;
         let user_id = env::var("BOT_USER_ID").context("missing bot user id in BOT_USER_ID")?;
-        let password = env::var("BOT_PWD").context("missing bot user id in BOT_PWD")?;
+        let password = env::var("BOT_PWD").ok();
+        let password_file = env::var_os("BOT_PWD_FILE").map(PathBuf::from);
+        let access_token = env::var("BOT_ACCESS_TOKEN").ok();
+        let device_id = env::var("BOT_DEVICE_ID").ok();
         let matrix_store_path =
             env::var("MATRIX_STORE_PATH").context("missing MATRIX_STORE_PATH")?;
         let redb_path = env::var("REDB_PATH").context("missing REDB_PATH")?;
@@ -118,17 +194,31 @@ impl BotConfig {
             .collect::<anyhow::Result<Vec<_>>>()
             .context("a module path isn't valid")?;
 
+        let migrations_dry_run = env::var("MIGRATIONS_DRY_RUN").is_ok_and(|val| val == "1");
+
         debug!("Using configuration from environment");
-        Ok(Self {
+        let mut config = Self {
             home_server,
             user_id,
             password,
+            password_file,
+            access_token,
+            device_id,
+            oauth: None,
             matrix_store_path,
             admin_user_id,
             redb_path,
             modules_paths,
             modules_config: None,
-        })
+            modules_permissions: None,
+            max_message_length: None,
+            modules_long_messages: None,
+            loop_protection: LoopProtectionConfig::default(),
+            migrations_dry_run,
+            config_path: None,
+        };
+        config.read_password_file()?;
+        Ok(config)
     }
 }
 
@@ -137,12 +227,15 @@ pub(crate) type ShareableDatabase = Arc<redb::Database>;
 struct AppCtx {
     modules: WasmModules,
     modules_paths: Vec<PathBuf>,
-    modules_config: HashMap<String, HashMap<String, String>>,
+    modules_config: HashMap<String, serde_json::Value>,
     engine: wasmtime::Engine,
-    needs_recompile: bool,
+    /// Paths of the module files that changed since the last hot reload.
+    pending_reloads: HashSet<PathBuf>,
     admin_user_id: OwnedUserId,
-    db: ShareableDatabase,
+    host: HostContext,
     room_resolver: RoomResolver,
+    client: Client,
+    outbox: Arc<Outbox>,
 }
 
 impl AppCtx {
@@ -150,72 +243,175 @@ impl AppCtx {
     pub fn new(
         client: Client,
         modules_paths: Vec<PathBuf>,
-        modules_config: HashMap<String, HashMap<String, String>>,
+        modules_config: HashMap<String, serde_json::Value>,
+        modules_permissions: HashMap<String, Vec<Permission>>,
         db: ShareableDatabase,
         admin_user_id: OwnedUserId,
+        outbox: Arc<Outbox>,
     ) -> anyhow::Result<Self> {
-        let room_resolver = RoomResolver::new(client);
+        let room_resolver = RoomResolver::new(client.clone());
+        let engine = wasm::create_engine()?;
 
-        let mut config = wasmtime::Config::new();
-        config.wasm_component_model(true);
-        config.cache_config_load_default()?;
+        let host = HostContext {
+            db,
+            client: Some(client.clone()),
+            permissions: modules_permissions,
+        };
 
-        let engine = wasmtime::Engine::new(&config)?;
+        let (modules, failures) = WasmModules::new(&engine, &host, &modules_paths, &modules_config);
+        report_load_failures(&outbox, &client, &admin_user_id, failures);
 
         Ok(Self {
-            modules: WasmModules::new(&engine, db.clone(), &modules_paths, &modules_config)?,
+            modules,
             modules_paths,
             modules_config,
-            needs_recompile: false,
+            pending_reloads: HashSet::new(),
             admin_user_id,
-            db,
+            host,
             room_resolver,
+            client,
             engine,
+            outbox,
         })
     }
 
-    pub async fn set_needs_recompile(ptr: Arc<Mutex<Self>>) {
+    /// Schedule a reload of the modules affected by changes to the given paths.
+    ///
+    /// Reloads are debounced: changes happening in a short time frame are batched together.
+    pub async fn schedule_reload(ptr: Arc<Mutex<Self>>, paths: Vec<PathBuf>) {
         {
-            let need = &mut ptr.lock().await.needs_recompile;
-            if *need {
+            let pending = &mut ptr.lock().await.pending_reloads;
+            let reload_scheduled = !pending.is_empty();
+            pending.extend(paths);
+            if reload_scheduled {
                 return;
             }
-            *need = true;
         }
 
-        let mut ptr = futures::executor::block_on(async {
-            tokio::time::sleep(Duration::new(1, 0)).await;
-            ptr.lock().await
+        tokio::time::sleep(Duration::new(1, 0)).await;
+
+        // Loading the modules is slow and blocking, so it's done outside of the async runtime.
+        let inner = ptr.clone();
+        let reload = tokio::task::spawn_blocking(move || {
+            let ctx = &mut *futures::executor::block_on(inner.lock());
+            let paths = std::mem::take(&mut ctx.pending_reloads);
+            ctx.modules.reload(
+                &ctx.engine,
+                &ctx.host,
+                paths,
+                &ctx.modules_paths,
+                &ctx.modules_config,
+            )
         });
 
-        match WasmModules::new(
-            &ptr.engine,
-            ptr.db.clone(),
-            &ptr.modules_paths,
-            &ptr.modules_config,
-        ) {
-            Ok(modules) => {
-                ptr.modules = modules;
-                info!("successful hot reload!");
-            }
-            Err(err) => {
-                error!("hot reload failed: {err:#}");
+        match reload.await {
+            Ok(failures) if failures.is_empty() => info!("successful hot reload!"),
+            Ok(failures) => {
+                error!("hot reload failed for {} module(s)", failures.len());
+                let ctx = ptr.lock().await;
+                report_load_failures(&ctx.outbox, &ctx.client, &ctx.admin_user_id, failures);
             }
+            Err(err) => error!("couldn't reload the modules: {err}"),
+        }
+    }
+}
+
+/// Report the modules that failed to load to the admin, in a direct message.
+fn report_load_failures(
+    outbox: &Arc<Outbox>,
+    client: &Client,
+    admin_user_id: &UserId,
+    failures: Vec<LoadFailure>,
+) {
+    if failures.is_empty() {
+        return;
+    }
+
+    let mut text = String::from("Some modules couldn't be loaded:");
+    for failure in failures {
+        text.push_str(&format!("\n- {failure}"));
+    }
+
+    notify_admin(outbox, client, admin_user_id, text);
+}
+
+/// Send a message to the admin, in a direct message, in the background.
+fn notify_admin(outbox: &Arc<Outbox>, client: &Client, admin_user_id: &UserId, text: String) {
+    let outbox = outbox.clone();
+    let client = client.clone();
+    let admin_user_id = admin_user_id.to_owned();
+    tokio::spawn(async move {
+        let result = async {
+            let room = direct_room(&client, &admin_user_id).await?;
+            outbox
+                .send(&room, RoomMessageEventContent::text_plain(text))
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        if let Err(err) = result {
+            error!("couldn't send a message to the admin: {err:#}");
         }
+    });
+}
 
-        ptr.needs_recompile = false;
+/// Returns the direct message room with the given user, creating it if needs be.
+async fn direct_room(client: &Client, user_id: &UserId) -> anyhow::Result<Room> {
+    if let Some(room) = client.get_dm_room(user_id) {
+        return Ok(room);
     }
+
+    debug!("creating a direct message room with {user_id}...");
+    Ok(client.create_dm(user_id).await?)
 }
 
 #[derive(Clone)]
 struct App {
     inner: Arc<Mutex<AppCtx>>,
+    loop_protection: Arc<std::sync::Mutex<LoopProtection>>,
+    outbox: Arc<Outbox>,
+    long_messages: Arc<LongMessages>,
 }
 
 impl App {
-    pub fn new(ctx: AppCtx) -> Self {
+    pub fn new(
+        ctx: AppCtx,
+        loop_protection: LoopProtection,
+        outbox: Arc<Outbox>,
+        long_messages: LongMessages,
+    ) -> Self {
         Self {
             inner: Arc::new(Mutex::new(ctx)),
+            loop_protection: Arc::new(std::sync::Mutex::new(loop_protection)),
+            outbox,
+            long_messages: Arc::new(long_messages),
+        }
+    }
+}
+
+/// What to do in response to a message.
+#[derive(Default)]
+struct Outcome {
+    /// Actions to run in the room the message was received in.
+    actions: Vec<wasm::Action>,
+    /// Messages meant for the sender only, sent in a direct message.
+    private: Vec<wasm::Message>,
+    /// Name of the module which produced the actions, if any; the events sent on its behalf are
+    /// recorded, so that replies to them are routed back to it.
+    module: Option<String>,
+    /// When handling an edit, the events the module sent in response to the original message;
+    /// they're replaced by the new responses, in order.
+    replaces: Vec<String>,
+}
+
+impl From<Vec<wasm::Action>> for Outcome {
+    fn from(actions: Vec<wasm::Action>) -> Self {
+        Self {
+            actions,
+            private: Vec::new(),
+            module: None,
+            replaces: Vec::new(),
         }
     }
 }
@@ -228,9 +424,7 @@ fn try_handle_admin<'a>(
     modules: impl Iterator<Item = &'a mut Module>,
     room_resolver: &mut RoomResolver,
 ) -> Option<Vec<wasm::Action>> {
-    let Some(rest) = content.strip_prefix("!admin") else {
-        return None;
-    };
+    let rest = content.strip_prefix("!admin")?;
 
     trace!("trying admin for {content}");
 
@@ -248,6 +442,10 @@ fn try_handle_admin<'a>(
                 Ok(None) | Err(_) => (room.to_string(), format!("{} {}", possible_room, rest)),
             };
 
+            if module == "trinity" {
+                return Some(handle_trinity_admin(rest.trim(), sender, modules));
+            }
+
             let mut found = None;
             for m in modules {
                 if m.name() == module {
@@ -265,32 +463,84 @@ fn try_handle_admin<'a>(
         } else {
             Some(vec![wasm::Action::Respond(wasm::Message {
                 text: "missing command".to_owned(),
-                html: None,
+                format: wasm::MessageFormat::Plain,
                 to: sender.to_string(),
+                mentions: Vec::new(),
+                mention_room: false,
             })])
         }
     } else {
         Some(vec![wasm::Action::Respond(wasm::Message {
             text: "missing module and command".to_owned(),
-            html: None,
+            format: wasm::MessageFormat::Plain,
             to: sender.to_string(),
+            mentions: Vec::new(),
+            mention_room: false,
         })])
     }
 }
 
+/// Handles the admin commands targeting the host itself, i.e. `!admin trinity ...`.
+fn handle_trinity_admin<'a>(
+    cmd: &str,
+    sender: &UserId,
+    modules: impl Iterator<Item = &'a mut Module>,
+) -> Vec<wasm::Action> {
+    let text = match cmd {
+        "modules" => {
+            let mut text = String::from("Loaded modules:");
+            for m in modules {
+                let manifest = m.manifest();
+                text.push_str(&format!(
+                    "\n- {}: {} v{}",
+                    m.name(),
+                    manifest.name,
+                    manifest.version
+                ));
+                if let Some(author) = &manifest.author {
+                    text.push_str(&format!(" by {author}"));
+                }
+                if !manifest.commands.is_empty() {
+                    text.push_str(&format!("; commands: {}", manifest.commands.join(", ")));
+                }
+                let capabilities = manifest
+                    .capabilities
+                    .iter()
+                    .map(|capability| capability.to_string())
+                    .collect::<Vec<_>>();
+                if !capabilities.is_empty() {
+                    text.push_str(&format!("; capabilities: {}", capabilities.join(", ")));
+                }
+            }
+            text
+        }
+        _ => "unknown command! available: modules".to_owned(),
+    };
+
+    vec![wasm::Action::Respond(wasm::Message {
+        text,
+        format: wasm::MessageFormat::Plain,
+        to: sender.to_string(),
+        mentions: Vec::new(),
+        mention_room: false,
+    })]
+}
+
+/// Help messages longer than this, in bytes, are sent to the sender in a direct message rather
+/// than in the room.
+const MAX_ROOM_HELP_LENGTH: usize = 2048;
+
 fn try_handle_help<'a>(
     content: &str,
     sender: &UserId,
     modules: impl Iterator<Item = &'a mut Module>,
-) -> Option<wasm::Action> {
-    let Some(rest) = content.strip_prefix("!help") else {
-        return None;
-    };
+) -> Option<Outcome> {
+    let rest = content.strip_prefix("!help")?;
 
     // Special handling for help messages.
-    let (msg, html) = if rest.trim().is_empty() {
+    let (msg, format) = if rest.trim().is_empty() {
         let mut msg = String::from("Available modules:");
-        let mut html = String::from("Available modules: <ul>");
+        let mut html = String::from("<p>Available modules:</p><ul>");
         for m in modules {
             let help = match m.help(None) {
                 Ok(msg) => Some(msg),
@@ -301,45 +551,92 @@ fn try_handle_help<'a>(
             }
             .unwrap_or("<missing>".to_string());
 
-            msg.push_str(&format!("\n- {name}: {help}", name = m.name(), help = help));
-            // TODO lol sanitize html
-            html.push_str(&format!(
-                "<li><b>{name}</b>: {help}</li>",
+            let commands = &m.manifest().commands;
+            let commands = if commands.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", commands.join(", "))
+            };
+
+            msg.push_str(&format!(
+                "\n- {name}{commands}: {help}",
                 name = m.name(),
                 help = help
             ));
+            html.push_str(&format!(
+                "<li><b>{name}</b>{commands}: {help}</li>",
+                name = html::escape(m.name()),
+                commands = html::escape(&commands),
+                help = html::render_markdown_inline(&help)
+            ));
         }
         html.push_str("</ul>");
 
-        (msg, html)
+        (msg, wasm::MessageFormat::Html(html))
     } else if let Some(rest) = rest.strip_prefix(' ') {
-        let rest = rest.trim();
-        let (module, topic) = rest
-            .split_once(' ')
-            .map(|(l, r)| (l, Some(r.trim())))
-            .unwrap_or((rest, None));
+        // Topics can be nested, e.g. `!help module topic subtopic`.
+        let mut words = rest.split_whitespace();
+        let module = words.next().unwrap_or_default();
+        let topic = words.collect::<Vec<_>>().join(" ");
+        let topic = (!topic.is_empty()).then_some(topic);
+
         let mut found = None;
         for m in modules {
             if m.name() == module {
-                found = m.help(topic).ok();
+                found = match topic_help(m, topic.as_deref()) {
+                    Ok(help) => Some(help),
+                    Err(err) => {
+                        error!("error when handling help command: {err:#}");
+                        None
+                    }
+                };
                 break;
             }
         }
-        let msg = if let Some(content) = found {
-            content
+
+        if let Some(content) = found {
+            (content, wasm::MessageFormat::Markdown)
         } else {
-            format!("module {module} not found")
-        };
-        (msg.clone(), msg)
+            (
+                format!("module {module} not found"),
+                wasm::MessageFormat::Plain,
+            )
+        }
     } else {
         return None;
     };
 
-    return Some(wasm::Action::Respond(wasm::Message {
+    let msg = wasm::Message {
         text: msg,
-        html: Some(html),
+        format,
         to: sender.to_string(), // TODO rather room?
-    }));
+        mentions: Vec::new(),
+        mention_room: false,
+    };
+
+    if msg.text.len() > MAX_ROOM_HELP_LENGTH {
+        return Some(Outcome {
+            private: vec![msg],
+            ..Default::default()
+        });
+    }
+
+    Some(vec![wasm::Action::Respond(msg)].into())
+}
+
+/// Returns the module's help for the given topic, falling back to the closest parent topic for
+/// which the module has something to say.
+fn topic_help(module: &mut Module, topic: Option<&str>) -> anyhow::Result<String> {
+    let mut topic = topic;
+    loop {
+        let help = module.help(topic)?;
+        match topic {
+            Some(current) if help.trim().is_empty() => {
+                topic = current.rsplit_once(' ').map(|(parent, _)| parent);
+            }
+            _ => return Ok(help),
+        }
+    }
 }
 
 /// After a room has been upgraded, automatically attempt to join the new room.
@@ -376,6 +673,53 @@ async fn on_room_upgrade(ev: OriginalSyncRoomTombstoneEvent, room: Room) {
     }
 }
 
+/// How long a module can take to handle a message, before the bot shows it's typing.
+const TYPING_NOTICE_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// How often the typing notice is renewed, while a module is still handling a message.
+const TYPING_NOTICE_RENEWAL: Duration = Duration::from_secs(3);
+
+/// The typing notice of the bot in a room, cleared when dropped if it's shown.
+struct TypingNotice {
+    room: Room,
+    shown: bool,
+}
+
+impl TypingNotice {
+    fn new(room: Room) -> Self {
+        Self { room, shown: false }
+    }
+
+    /// Shows the typing notice, or renews it if it's already shown.
+    async fn show(&mut self) {
+        self.shown = true;
+        if let Err(err) = self.room.typing_notice(true).await {
+            warn!("couldn't send typing notice: {err}");
+        }
+    }
+
+    async fn clear(&mut self) {
+        if std::mem::take(&mut self.shown)
+            && let Err(err) = self.room.typing_notice(false).await
+        {
+            warn!("couldn't clear typing notice: {err}");
+        }
+    }
+}
+
+impl Drop for TypingNotice {
+    fn drop(&mut self) {
+        if self.shown {
+            let room = self.room.clone();
+            tokio::spawn(async move {
+                if let Err(err) = room.typing_notice(false).await {
+                    warn!("couldn't clear typing notice: {err}");
+                }
+            });
+        }
+    }
+}
+
 async fn on_message(
     ev: OriginalSyncRoomMessageEvent,
     room: Room,
@@ -392,11 +736,40 @@ async fn on_message(
         return Ok(());
     }
 
-    let content = if let MessageType::Text(text) = &ev.content.msgtype {
-        text.body.to_string()
-    } else {
-        // Ignore other kinds of messages at the moment.
-        return Ok(());
+    let (ignore_notices, sender_is_bot) = {
+        let loop_protection = &mut *ctx.loop_protection.lock().unwrap();
+
+        if loop_protection.is_ignored(&ev.sender) {
+            trace!("ignoring message from {}", ev.sender);
+            return Ok(());
+        }
+
+        if loop_protection.is_paused(room.room_id()) {
+            trace!("ignoring message in paused room {}", room.room_id());
+            return Ok(());
+        }
+
+        (
+            loop_protection.ignore_notices(),
+            loop_protection.is_bot(&ev.sender),
+        )
+    };
+
+    // Edits carry the new content of the message separately, the main content being a fallback
+    // for clients which don't support them.
+    let msgtype = match &ev.content.relates_to {
+        Some(Relation::Replacement(replacement)) => &replacement.new_content.msgtype,
+        _ => &ev.content.msgtype,
+    };
+
+    let is_notice = matches!(msgtype, MessageType::Notice(_));
+    let content = match msgtype {
+        MessageType::Text(text) => text.body.to_string(),
+        MessageType::Notice(notice) if !ignore_notices => notice.body.to_string(),
+        _ => {
+            // Ignore other kinds of messages at the moment.
+            return Ok(());
+        }
     };
 
     trace!(
@@ -410,14 +783,102 @@ async fn on_message(
     // TODO Use a lock-free data-structure for the list of modules + put locks in the module
     // internal implementation?
     // TODO or create a new wasm instance per message \o/
+    let loop_protection = ctx.loop_protection.clone();
+    let outbox = ctx.outbox.clone();
+    let long_messages = ctx.long_messages.clone();
     let ctx = ctx.inner.clone();
     let room_id = room.room_id().to_owned();
 
-    let sender = ev.sender;
+    // Gather the thread information, if the event's in a thread, so as to be able to reply in the
+    // thread later.
+    let thread_info = if let Some(Relation::Thread(thread)) = &ev.content.relates_to {
+        Some(Thread::plain(thread.event_id.clone(), ev.event_id.clone()))
+    } else {
+        None
+    };
+
+    let sender = ev.sender.clone();
+    let thread_root = thread_info.as_ref().map(|thread| thread.event_id.clone());
+
+    let message = wasm::InboundMessage {
+        content: content.clone(),
+        event_id: ev.event_id.to_string(),
+        timestamp: ev.origin_server_ts.0.into(),
+        sender_id: ev.sender.to_string(),
+        sender_name: match room.get_member_no_sync(&ev.sender).await {
+            Ok(member) => member.and_then(|member| member.display_name().map(ToOwned::to_owned)),
+            Err(err) => {
+                warn!("couldn't get member {}: {err}", ev.sender);
+                None
+            }
+        },
+        room: room_id.to_string(),
+        is_direct: room.is_direct().await?,
+        thread_root: thread_root.as_ref().map(ToString::to_string),
+        reply_to: match &ev.content.relates_to {
+            Some(Relation::Reply { in_reply_to }) => Some(in_reply_to.event_id.to_string()),
+            Some(Relation::Thread(thread)) if !thread.is_falling_back => thread
+                .in_reply_to
+                .as_ref()
+                .map(|in_reply_to| in_reply_to.event_id.to_string()),
+            _ => None,
+        },
+        edit_of: match &ev.content.relates_to {
+            Some(Relation::Replacement(replacement)) => Some(replacement.event_id.to_string()),
+            _ => None,
+        },
+    };
+
+    let db = ctx.lock().await.host.db.clone();
 
-    let new_actions = tokio::task::spawn_blocking(move || {
+    // Replies to the modules' messages from humans are part of a conversation, not of a loop.
+    let may_loop = sender_is_bot
+        || is_notice
+        || !message.reply_to.as_deref().is_some_and(|event_id| {
+            authored_events::author(&db, event_id)
+                .unwrap_or_else(|err| {
+                    warn!("couldn't look up the author of {event_id}: {err:#}");
+                    None
+                })
+                .is_some()
+        });
+
+    // Set by the dispatch when a module starts handling the message.
+    let (invoking_tx, mut invoking) = tokio::sync::watch::channel(false);
+
+    let mut dispatch = tokio::task::spawn_blocking(move || {
         let ctx = &mut *futures::executor::block_on(ctx.lock());
 
+        // Edits only go to the module which responded to the original message, if any, and are
+        // ignored otherwise.
+        if let Some(original) = &message.edit_of {
+            let response =
+                authored_events::response(&ctx.host.db, original).unwrap_or_else(|err| {
+                    warn!("couldn't look up the response to {original}: {err:#}");
+                    None
+                });
+            let Some(response) = response else {
+                trace!("ignoring edit of {original}");
+                return Outcome::default();
+            };
+            let Some(module) = ctx.modules.iter_mut().find(|m| m.name() == response.module) else {
+                return Outcome::default();
+            };
+            trace!("trying to handle edit with {}...", response.module);
+            let _ = invoking_tx.send(true);
+            return match module.edit(&message, original, &room_id, thread_root.as_deref()) {
+                Ok(actions) => Outcome {
+                    module: Some(response.module),
+                    replaces: response.events,
+                    ..actions.into()
+                },
+                Err(err) => {
+                    warn!("wasm module {} ran into an error: {err}", response.module);
+                    Outcome::default()
+                }
+            };
+        }
+
         if sender == ctx.admin_user_id {
             match try_handle_admin(
                 &content,
@@ -429,24 +890,51 @@ async fn on_message(
                 None => {}
                 Some(actions) => {
                     trace!("handled by admin, skipping modules");
-                    return actions;
+                    return actions.into();
                 }
             }
         }
 
-        if let Some(actions) = try_handle_help(&content, &sender, ctx.modules.iter_mut()) {
+        if let Some(outcome) = try_handle_help(&content, &sender, ctx.modules.iter_mut()) {
             trace!("handled by help, skipping modules");
-            return vec![actions];
+            return outcome;
+        }
+
+        // Replies to a module's message, or messages in a thread it started, go to that module
+        // first.
+        if let Some((author, in_reply_to)) = reply_target(&ctx.host.db, &message)
+            && let Some(module) = ctx.modules.iter_mut().find(|m| m.name() == author)
+        {
+            trace!("trying to handle reply with {author}...");
+            let _ = invoking_tx.send(true);
+            match module.reply(&message, &in_reply_to, &room_id, thread_root.as_deref()) {
+                Ok(actions) => {
+                    if !actions.is_empty() {
+                        trace!("{author} handled the reply");
+                        return Outcome {
+                            module: Some(author),
+                            ..actions.into()
+                        };
+                    }
+                }
+                Err(err) => {
+                    warn!("wasm module {author} ran into an error: {err}");
+                }
+            }
         }
 
-        for module in ctx.modules.iter_mut() {
+        for module in ctx.modules.iter_mut().filter(|m| m.wants(&content)) {
             trace!("trying to handle message with {}...", module.name());
-            match module.handle(&content, &sender, &room_id) {
+            let _ = invoking_tx.send(true);
+            match module.handle(&message, &room_id, thread_root.as_deref()) {
                 Ok(actions) => {
                     if !actions.is_empty() {
                         // TODO support handling the same message with several handlers.
                         trace!("{} returned a response!", module.name());
-                        return actions;
+                        return Outcome {
+                            module: Some(module.name().to_owned()),
+                            ..actions.into()
+                        };
                     }
                 }
                 Err(err) => {
@@ -455,48 +943,340 @@ async fn on_message(
             }
         }
 
-        Vec::new()
-    })
-    .await?;
+        Outcome::default()
+    });
 
-    // Gather the thread information, if the event's in a thread, so as to be able to reply in the
-    // thread later.
-    let thread_info = if let Some(Relation::Thread(thread)) = ev.content.relates_to {
-        Some(Thread::plain(thread.event_id, ev.event_id.clone()))
-    } else {
-        None
+    // Let the users know the bot is working on it, if a module takes a while to handle the
+    // message; the typing notice needs to be renewed periodically, as it expires on its own.
+    let mut typing = TypingNotice::new(room.clone());
+    let mut invoked = false;
+    let outcome = loop {
+        let delay = if typing.shown {
+            TYPING_NOTICE_RENEWAL
+        } else {
+            TYPING_NOTICE_THRESHOLD
+        };
+        tokio::select! {
+            outcome = &mut dispatch => break outcome?,
+            Ok(()) = invoking.changed(), if !invoked => invoked = true,
+            _ = sleep(delay), if invoked => typing.show().await,
+        }
     };
 
-    let new_events: Vec<AnyMessageLikeEventContent> = new_actions
-        .into_iter()
-        .map(|a| match a {
+    let Outcome {
+        actions: mut new_actions,
+        private,
+        module,
+        replaces,
+    } = outcome;
+
+    // The message the actions respond to, that is, the original message in the case of an edit.
+    let trigger = match &ev.content.relates_to {
+        Some(Relation::Replacement(replacement)) => replacement.event_id.clone(),
+        _ => ev.event_id.clone(),
+    };
+
+    // Remember which module sent an event, so replies to it can be routed back to the module.
+    let record_author = |event_id: &OwnedEventId| {
+        if let Some(module) = &module
+            && let Err(err) = authored_events::record(&db, event_id.as_str(), module)
+        {
+            warn!("couldn't record the author of {event_id}: {err:#}");
+        }
+    };
+
+    // Private messages are sent in the room directly if it's already a direct conversation, and
+    // in a direct message with a notice in the room otherwise.
+    let mut direct = None;
+    if !private.is_empty() {
+        if room.is_direct().await? {
+            new_actions.extend(private.into_iter().map(wasm::Action::Respond));
+        } else {
+            new_actions.push(wasm::Action::Respond(wasm::Message {
+                text: "The answer is a bit long, so I've sent it to you in a direct message."
+                    .to_owned(),
+                format: wasm::MessageFormat::Plain,
+                to: ev.sender.to_string(),
+                mentions: Vec::new(),
+                mention_room: false,
+            }));
+            direct = Some(private);
+        }
+    }
+
+    // Responses that are too long are split, collapsed or uploaded as files, depending on the
+    // module.
+    let mut fitted = Vec::with_capacity(new_actions.len());
+    for action in new_actions {
+        match action {
             wasm::Action::Respond(msg) => {
-                let mut new_event = if let Some(html) = msg.html {
-                    RoomMessageEventContent::text_html(msg.text, html)
-                } else {
-                    RoomMessageEventContent::text_plain(msg.text)
-                };
+                let mentioned = mentioned_users(&room, &msg.mentions).await;
+                fitted.extend(long_messages.fit(module.as_deref(), msg, &mentioned));
+            }
+            action => fitted.push(action),
+        }
+    }
+    let new_actions = fitted;
+
+    let mut replaces = replaces.into_iter();
+    let mut responses = Vec::new();
+
+    for action in new_actions {
+        match action {
+            wasm::Action::Respond(msg) => {
+                let mut new_event = message_content(&room, msg).await;
+
+                // Update the previous response, if there's one, rather than sending a new one.
+                if let Some(previous) = replaces.next() {
+                    let previous = EventId::parse(&previous)?;
+                    let mentions = new_event.mentions.clone();
+                    new_event = new_event.make_replacement(
+                        ReplacementMetadata::new(previous.clone(), mentions),
+                        None,
+                    );
+                    if let Err(err) = outbox.send(&room, new_event).await {
+                        warn!("couldn't update response {previous}: {err:#}");
+                    }
+                    responses.push(previous);
+                    continue;
+                }
 
                 // Forward the thread information, if available.
                 if let Some(thread_info) = &thread_info {
                     new_event.relates_to = Some(Relation::Thread(thread_info.clone()));
                 }
 
-                new_event.into()
+                match outbox.send(&room, new_event).await {
+                    Ok(event_id) => {
+                        record_author(&event_id);
+                        responses.push(event_id);
+                    }
+                    Err(err) => warn!("couldn't send response: {err:#}"),
+                }
             }
             wasm::Action::React(reaction) => {
-                ReactionEventContent::new(Annotation::new(ev.event_id.clone(), reaction)).into()
+                let reaction =
+                    ReactionEventContent::new(Annotation::new(trigger.clone(), reaction));
+                if let Err(err) = outbox.send(&room, reaction).await {
+                    warn!("couldn't send reaction: {err:#}");
+                }
             }
-        })
-        .collect::<Vec<_>>();
+            wasm::Action::Attach(attachment) => {
+                let sent = async {
+                    let mut new_event = upload_attachment(&client, &room, attachment).await?;
+                    if let Some(thread_info) = &thread_info {
+                        new_event.relates_to = Some(Relation::Thread(thread_info.clone()));
+                    }
+                    outbox.send(&room, new_event).await
+                }
+                .await;
+
+                match sent {
+                    Ok(event_id) => {
+                        record_author(&event_id);
+                        responses.push(event_id);
+                    }
+                    Err(err) => warn!("couldn't send attachment: {err:#}"),
+                }
+            }
+        }
+    }
+
+    // Remember the response, so it can be updated if the message is edited later; previous
+    // responses which haven't been replaced are kept, so a later edit can still update them.
+    if let Some(module) = module
+        && !responses.is_empty()
+    {
+        let response = authored_events::Response {
+            module,
+            events: responses
+                .iter()
+                .map(ToString::to_string)
+                .chain(replaces)
+                .collect(),
+        };
+        if let Err(err) = authored_events::record_response(&db, trigger.as_str(), &response) {
+            warn!("couldn't record the response to {trigger}: {err:#}");
+        }
+    }
+
+    let paused =
+        loop_protection
+            .lock()
+            .unwrap()
+            .on_handled(room.room_id(), !responses.is_empty(), may_loop);
+    if paused {
+        warn!(
+            "too many messages sent in {}, pausing the room",
+            room.room_id()
+        );
+        let notice = RoomMessageEventContent::notice_plain(
+            "It looks like I'm stuck in a loop with another bot, so I'll stay quiet in this room \
+            for a while.",
+        );
+        outbox.send(&room, notice).await?;
+    }
 
-    for event in new_events {
-        room.send(event).await?;
+    typing.clear().await;
+
+    if let Some(private) = direct {
+        let dm = direct_room(&client, &ev.sender).await?;
+        for msg in private {
+            let mentioned = mentioned_users(&room, &msg.mentions).await;
+            for msg in long_messages.split(msg, &mentioned) {
+                outbox.send(&dm, message_content(&room, msg).await).await?;
+            }
+        }
     }
 
     Ok(())
 }
 
+/// Uploads an attachment to the media repository, encrypted if the room is, and returns the
+/// content of the message referencing it.
+async fn upload_attachment(
+    client: &Client,
+    room: &Room,
+    attachment: wasm::Attachment,
+) -> anyhow::Result<RoomMessageEventContent> {
+    let content_type: mime::Mime = attachment
+        .mimetype
+        .parse()
+        .with_context(|| format!("invalid mimetype {}", attachment.mimetype))?;
+    let size = UInt::try_from(attachment.data.len()).context("attachment is too large")?;
+    let encrypted = room.latest_encryption_state().await?.is_encrypted();
+
+    let source = upload_media(client, encrypted, &content_type, attachment.data).await?;
+    let body = attachment.filename;
+
+    let type_ = content_type.type_();
+    let msgtype = if type_ == mime::IMAGE {
+        let mut info = ImageInfo::new();
+        info.mimetype = Some(content_type.to_string());
+        info.size = Some(size);
+        info.width = attachment.width.map(UInt::from);
+        info.height = attachment.height.map(UInt::from);
+
+        if let Some(thumbnail) = attachment.thumbnail {
+            let thumbnail_type: mime::Mime = thumbnail
+                .mimetype
+                .parse()
+                .with_context(|| format!("invalid thumbnail mimetype {}", thumbnail.mimetype))?;
+            let mut thumbnail_info = ThumbnailInfo::new();
+            thumbnail_info.mimetype = Some(thumbnail_type.to_string());
+            thumbnail_info.size =
+                Some(UInt::try_from(thumbnail.data.len()).context("thumbnail is too large")?);
+            thumbnail_info.width = Some(thumbnail.width.into());
+            thumbnail_info.height = Some(thumbnail.height.into());
+
+            info.thumbnail_source =
+                Some(upload_media(client, encrypted, &thumbnail_type, thumbnail.data).await?);
+            info.thumbnail_info = Some(Box::new(thumbnail_info));
+        }
+
+        MessageType::Image(ImageMessageEventContent::new(body, source).info(Box::new(info)))
+    } else if type_ == mime::AUDIO {
+        let mut info = AudioInfo::new();
+        info.mimetype = Some(content_type.to_string());
+        info.size = Some(size);
+        MessageType::Audio(AudioMessageEventContent::new(body, source).info(Box::new(info)))
+    } else if type_ == mime::VIDEO {
+        let mut info = VideoInfo::new();
+        info.mimetype = Some(content_type.to_string());
+        info.size = Some(size);
+        MessageType::Video(VideoMessageEventContent::new(body, source).info(Box::new(info)))
+    } else {
+        let mut info = FileInfo::new();
+        info.mimetype = Some(content_type.to_string());
+        info.size = Some(size);
+        MessageType::File(FileMessageEventContent::new(body, source).info(Box::new(info)))
+    };
+
+    Ok(RoomMessageEventContent::new(msgtype))
+}
+
+/// Uploads a file to the media repository, encrypting it first if needs be.
+async fn upload_media(
+    client: &Client,
+    encrypted: bool,
+    content_type: &mime::Mime,
+    data: Vec<u8>,
+) -> anyhow::Result<MediaSource> {
+    if encrypted {
+        let file = client
+            .upload_encrypted_file(&mut std::io::Cursor::new(data))
+            .await?;
+        Ok(MediaSource::Encrypted(Box::new(file)))
+    } else {
+        let response = client.media().upload(content_type, data, None).await?;
+        Ok(MediaSource::Plain(response.content_uri))
+    }
+}
+
+/// Finds the module which sent the event the message replies to, or the root of its thread.
+///
+/// Returns the module's name along with the ID of that event.
+fn reply_target(
+    db: &ShareableDatabase,
+    message: &wasm::InboundMessage,
+) -> Option<(String, String)> {
+    for event_id in [&message.reply_to, &message.thread_root]
+        .into_iter()
+        .flatten()
+    {
+        match authored_events::author(db, event_id) {
+            Ok(Some(module)) => return Some((module, event_id.clone())),
+            Ok(None) => {}
+            Err(err) => warn!("couldn't look up the author of {event_id}: {err:#}"),
+        }
+    }
+    None
+}
+
+/// Creates the event for a message, rendering Markdown, sanitizing HTML and turning mentions into
+/// links to the users, as needed.
+///
+/// Display names of the mentioned users are looked up in the given room.
+async fn message_content(room: &Room, msg: wasm::Message) -> RoomMessageEventContent {
+    let mentioned = mentioned_users(room, &msg.mentions).await;
+    let (text, html) = html::render_message(&msg, &mentioned);
+
+    let mut content = if let Some(html) = html {
+        RoomMessageEventContent::text_html(text, html)
+    } else {
+        RoomMessageEventContent::text_plain(text)
+    };
+
+    if !mentioned.is_empty() || msg.mention_room {
+        let mut mentions =
+            Mentions::with_user_ids(mentioned.into_iter().map(|(user_id, _)| user_id));
+        mentions.room = msg.mention_room;
+        content.mentions = Some(mentions);
+    }
+
+    content
+}
+
+/// Resolves the users mentioned in a message, along with their display names in the room.
+async fn mentioned_users(room: &Room, mentions: &[String]) -> Vec<html::Mentioned> {
+    let mut mentioned = Vec::with_capacity(mentions.len());
+    for user_id in mentions {
+        let Ok(user_id) = UserId::parse(user_id) else {
+            warn!("ignoring mention of invalid user id {user_id}");
+            continue;
+        };
+        let name = match room.get_member_no_sync(&user_id).await {
+            Ok(member) => member.and_then(|member| member.display_name().map(ToOwned::to_owned)),
+            Err(err) => {
+                warn!("couldn't get member {user_id}: {err}");
+                None
+            }
+        };
+        mentioned.push((user_id, name));
+    }
+    mentioned
+}
+
 /// Autojoin mixin.
 async fn on_stripped_state_member(
     room_member: StrippedRoomMemberEvent,
@@ -541,82 +1321,43 @@ async fn on_stripped_state_member(
     }
 }
 
-async fn run_migrations(config: &BotConfig, db: &ShareableDatabase) -> anyhow::Result<()> {
-    let version = admin_table::read_u64(&db, admin_table::VERSION_ENTRY)
-        .context("reading version from the database")?
-        .unwrap_or(0);
-
-    if version < 1 {
-        // If no version is set, we assume this is the first run, which happens after the sdk bump.
-        // In this case, the state is likely outdated in the database, so we remove the database
-        // directory first, and then drop the device id, so as to create a new one.
-        debug!("running data migration 1: removing old database directory and dropping device id");
-
-        // Remove the database directory.
-        if let Err(err) = fs::remove_dir_all(&config.matrix_store_path) {
-            warn!("failed to remove old database directory: {err:#}");
-        }
-
-        // Drop the device id, so that a new one is created on the next login.
-        if let Err(err) =
-            admin_table::remove(&db, DEVICE_ID_ENTRY).context("dropping device_id in the database")
-        {
-            warn!("failed to drop device_id: {err:#}");
-        }
-
-        // Bump the version to 1.
-        admin_table::write_u64(&db, admin_table::VERSION_ENTRY, 1)
-            .context("writing initial version into the database")?;
-    }
-
-    Ok(())
-}
-
 /// Run the client for the given `BotConfig`.
 pub async fn run(config: BotConfig) -> anyhow::Result<()> {
     // Create the database, and try to find a device id.
     let db = Arc::new(unsafe { redb::Database::create(config.redb_path.clone(), 1024 * 1024)? });
 
-    run_migrations(&config, &db).await?;
+    migrations::run(&config, &db, config.migrations_dry_run)?;
+
+    if config.migrations_dry_run {
+        let engine = wasm::create_engine()?;
+        let host = HostContext {
+            db,
+            client: None,
+            permissions: HashMap::new(),
+        };
+        WasmModules::dry_run_migrations(&engine, &host, &config.modules_paths)?;
+        info!("migrations dry run done, exiting.");
+        return Ok(());
+    }
+
+    if let Err(err) = authored_events::prune(&db) {
+        warn!("couldn't prune old authored events: {err:#}");
+    }
 
     let client = Client::builder()
         .server_name(config.home_server.as_str().try_into()?)
         .sqlite_store(&config.matrix_store_path, None)
+        .handle_refresh_tokens()
         .build()
         .await?;
 
     // First we need to log in.
-    debug!("logging in...");
-    let mut login_builder = client
-        .matrix_auth()
-        .login_username(&config.user_id, &config.password);
-
-    let mut db_device_id = None;
-    if let Some(device_id) = admin_table::read_str(&db, DEVICE_ID_ENTRY)
-        .context("reading device_id from the database")?
-    {
-        trace!("reusing previous device_id...");
-        // the login builder keeps a reference to the previous device id string, so can't clone
-        // db_device_id here, it has to outlive the login_builder.
-        db_device_id = Some(device_id);
-        login_builder = login_builder.device_id(db_device_id.as_ref().unwrap());
-    }
-
-    let resp = login_builder.send().await?;
+    login::login(&client, &config, &db).await?;
+    login::persist_session_changes(&client, db.clone());
 
-    let resp_device_id = resp.device_id.to_string();
-    if db_device_id.as_ref() != Some(&resp_device_id) {
-        match db_device_id {
-            Some(prev) => {
-                warn!("overriding device_id (previous was {prev}, new is {resp_device_id})")
-            }
-            None => debug!("storing new device_id for the first time..."),
-        }
-        admin_table::write_str(&db, DEVICE_ID_ENTRY, &resp_device_id)
-            .context("writing new device_id into the database")?;
-    }
-
-    let modules_config = config.modules_config.unwrap_or_else(HashMap::new);
+    let config_path = config.config_path.clone();
+    let modules_config = config.modules_config.unwrap_or_default();
+    let modules_permissions = config.modules_permissions.unwrap_or_default();
 
     client
         .user_id()
@@ -626,23 +1367,64 @@ pub async fn run(config: BotConfig) -> anyhow::Result<()> {
     // messages. If the `StateStore` finds saved state in the location given the
     // initial sync will be skipped in favor of loading state from the store.
     debug!("starting initial sync...");
-    client
-        .sync_with_callback(SyncSettings::default(), |_| async { LoopCtrl::Break })
-        .await
-        .unwrap();
+    let mut backoff = SyncBackoff::new();
+    loop {
+        let started = Instant::now();
+        match client
+            .sync_with_callback(SyncSettings::default(), |_| async { LoopCtrl::Break })
+            .await
+        {
+            Ok(()) => break,
+            Err(err) => {
+                if let Some(err) = backoff.retry(err, started).await {
+                    return Err(err.context("initial sync failed"));
+                }
+            }
+        }
+    }
 
     debug!("setting up app...");
     let client_copy = client.clone();
+    let outbox = Arc::new(Outbox::new(db.clone()));
     let app_ctx = AppCtx::new(
         client_copy,
         config.modules_paths,
         modules_config,
+        modules_permissions,
         db,
         config.admin_user_id,
+        outbox.clone(),
     )?;
-    let app = App::new(app_ctx);
-
-    let _watcher_guard = watcher(app.inner.clone()).await?;
+    let app = App::new(
+        app_ctx,
+        LoopProtection::new(config.loop_protection),
+        outbox.clone(),
+        LongMessages::new(
+            config
+                .max_message_length
+                .unwrap_or(long_messages::DEFAULT_MAX_MESSAGE_LENGTH),
+            config.modules_long_messages.unwrap_or_default(),
+        ),
+    );
+    let app_ctx = app.inner.clone();
+
+    // Send the events which couldn't be sent before the last shutdown.
+    outbox.resume(&client);
+
+    let modules_watcher = watcher(app.inner.clone()).await?;
+
+    // Reload the config when its file changes, or on SIGHUP.
+    let (reload_tx, reload_rx) = tokio::sync::mpsc::unbounded_channel();
+    let _config_watcher_guard = config_path
+        .as_deref()
+        .map(|path| config_reload::watch(path, reload_tx.clone()))
+        .transpose()?;
+    tokio::spawn(config_reload::run(
+        app.clone(),
+        config_path,
+        modules_watcher,
+        reload_rx,
+    ));
 
     debug!("setup ready! now listening to incoming messages.");
     client.add_event_handler_context(app);
@@ -650,17 +1432,14 @@ pub async fn run(config: BotConfig) -> anyhow::Result<()> {
     client.add_event_handler(on_room_upgrade);
     client.add_event_handler(on_stripped_state_member);
 
-    // Note: this method will never return.
-
-    tokio::select! {
-        _ = handle_signals() => {
-            // Exit :)
-        }
+    // Run until asked to exit, or until the sync fails for good.
+    let sync_error = tokio::select! {
+        _ = handle_signals(reload_tx) => None,
+        err = sync(&client) => Some(err),
+    };
 
-        Err(err) = client.sync(SyncSettings::default()) => {
-            anyhow::bail!(err);
-        }
-    }
+    debug!("shutting down modules...");
+    app_ctx.lock().await.modules.shutdown();
 
     // Set bot presence to offline.
     let request = matrix_sdk::ruma::api::client::presence::set_presence::v3::Request::new(
@@ -668,23 +1447,105 @@ pub async fn run(config: BotConfig) -> anyhow::Result<()> {
         PresenceState::Offline,
     );
 
+    if let Some(err) = sync_error {
+        // The session is likely not valid anymore, so this is best effort.
+        if let Err(presence_err) = client.send(request).await {
+            debug!("couldn't set presence to offline: {presence_err}");
+        }
+        return Err(err);
+    }
+
     client.send(request).await?;
 
     info!("properly exited, have a nice day!");
     Ok(())
 }
 
-async fn handle_signals() -> anyhow::Result<()> {
+/// Delay before restarting the sync after a failure, doubled after each failure in a row.
+const SYNC_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay before restarting the sync; a sync that ran for longer than this before failing
+/// is considered to have recovered, and the delay is reset.
+const SYNC_MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Exponential backoff between attempts to sync.
+struct SyncBackoff {
+    delay: Duration,
+}
+
+impl SyncBackoff {
+    fn new() -> Self {
+        Self {
+            delay: SYNC_INITIAL_BACKOFF,
+        }
+    }
+
+    /// Waits before retrying a sync that started at `started` and failed with `err`.
+    ///
+    /// Returns the error instead if it's unrecoverable, e.g. when the access token has been
+    /// invalidated or the device deleted.
+    async fn retry(&mut self, err: matrix_sdk::Error, started: Instant) -> Option<anyhow::Error> {
+        if let Some(kind) = err.client_api_error_kind()
+            && matches!(
+                kind,
+                ErrorKind::UnknownToken { .. }
+                    | ErrorKind::MissingToken
+                    | ErrorKind::UserDeactivated
+            )
+        {
+            error!(
+                "unrecoverable sync error, the bot's session isn't valid anymore (was the \
+                access token revoked, or the device deleted?): {err}"
+            );
+            return Some(err.into());
+        }
+
+        if started.elapsed() > SYNC_MAX_BACKOFF {
+            self.delay = SYNC_INITIAL_BACKOFF;
+        }
+
+        warn!("sync failed, restarting it in {:?}: {err}", self.delay);
+        sleep(self.delay).await;
+        self.delay = (self.delay * 2).min(SYNC_MAX_BACKOFF);
+        None
+    }
+}
+
+/// Runs the sync loop, restarting it with an exponential backoff when it fails.
+///
+/// Only returns in case of an unrecoverable error.
+async fn sync(client: &Client) -> anyhow::Error {
+    let mut backoff = SyncBackoff::new();
+
+    loop {
+        let started = Instant::now();
+        match client.sync(SyncSettings::default()).await {
+            Ok(()) => warn!("sync stopped unexpectedly, restarting it..."),
+            Err(err) => {
+                if let Some(err) = backoff.retry(err, started).await {
+                    return err;
+                }
+            }
+        }
+    }
+}
+
+/// Waits for a signal asking to exit; SIGHUP asks for the config to be reloaded instead.
+async fn handle_signals(reload: UnboundedSender<()>) -> anyhow::Result<()> {
     use futures::StreamExt as _;
     use signal_hook::consts::signal::*;
     use signal_hook_tokio::*;
 
-    let mut signals = Signals::new(&[SIGINT, SIGHUP, SIGQUIT, SIGTERM])?;
+    let mut signals = Signals::new([SIGINT, SIGHUP, SIGQUIT, SIGTERM])?;
     let handle = signals.handle();
 
     while let Some(signal) = signals.next().await {
         match signal {
-            SIGINT | SIGHUP | SIGQUIT | SIGTERM => {
+            SIGHUP => {
+                info!("received SIGHUP, reloading the config...");
+                let _ = reload.send(());
+            }
+            SIGINT | SIGQUIT | SIGTERM => {
                 handle.close();
                 break;
             }
@@ -697,55 +1558,52 @@ async fn handle_signals() -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn watcher(app: Arc<Mutex<AppCtx>>) -> anyhow::Result<Vec<notify::RecommendedWatcher>> {
+/// Watches the modules paths, reloading the modules whose file changed.
+///
+/// Paths can be added to or removed from the returned watcher, when the config changes.
+async fn watcher(app: Arc<Mutex<AppCtx>>) -> anyhow::Result<notify::RecommendedWatcher> {
     let modules_paths = { app.lock().await.modules_paths.clone() };
 
-    let mut watchers = Vec::with_capacity(modules_paths.len());
+    let rt_handle = tokio::runtime::Handle::current();
+    let mut watcher =
+        notify::recommended_watcher(move |res: Result<notify::Event, notify::Error>| match res {
+            Ok(event) => {
+                // Only watch wasm files
+                let paths: Vec<_> = event
+                    .paths
+                    .into_iter()
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+                    .collect();
+                if paths.is_empty() {
+                    return;
+                }
+
+                match event.kind {
+                    notify::EventKind::Create(_)
+                    | notify::EventKind::Modify(_)
+                    | notify::EventKind::Remove(_) => {
+                        // Trigger an update of the affected modules.
+                        let app = app.clone();
+                        rt_handle.spawn(async move {
+                            AppCtx::schedule_reload(app, paths).await;
+                        });
+                    }
+                    notify::EventKind::Access(_)
+                    | notify::EventKind::Any
+                    | notify::EventKind::Other => {}
+                }
+            }
+            Err(e) => warn!("watch error: {e:?}"),
+        })?;
+
     for modules_path in modules_paths {
         debug!(
             "setting up watcher on @ {}...",
             modules_path.to_string_lossy()
         );
-
-        let rt_handle = tokio::runtime::Handle::current();
-        let app = app.clone();
-        let mut watcher = notify::recommended_watcher(
-            move |res: Result<notify::Event, notify::Error>| match res {
-                Ok(event) => {
-                    // Only watch wasm files
-                    if !event.paths.iter().any(|path| {
-                        if let Some(ext) = path.extension() {
-                            ext == "wasm"
-                        } else {
-                            false
-                        }
-                    }) {
-                        return;
-                    }
-
-                    match event.kind {
-                        notify::EventKind::Create(_)
-                        | notify::EventKind::Modify(_)
-                        | notify::EventKind::Remove(_) => {
-                            // Trigger an update of the modules.
-                            let app = app.clone();
-                            rt_handle.spawn(async move {
-                                AppCtx::set_needs_recompile(app).await;
-                            });
-                        }
-                        notify::EventKind::Access(_)
-                        | notify::EventKind::Any
-                        | notify::EventKind::Other => {}
-                    }
-                }
-                Err(e) => warn!("watch error: {e:?}"),
-            },
-        )?;
-
         watcher.watch(&modules_path, RecursiveMode::Recursive)?;
-        watchers.push(watcher);
     }
 
     debug!("watcher setup done!");
-    Ok(watchers)
+    Ok(watcher)
 }
@@ -0,0 +1,327 @@
+//! Authentication of the bot against its homeserver.
+//!
+//! The bot can log in with a password, use a pre-issued access token, or log in through OAuth
+//! for homeservers that require it. Sessions obtained by logging in are persisted in the database,
+//! along with their refreshed tokens, so that they're restored on the next start, instead of
+//! logging in again.
+
+use std::io::IsTerminal as _;
+
+use anyhow::Context as _;
+use matrix_sdk::{
+    AuthSession, Client, SessionChange, SessionMeta, SessionTokens,
+    authentication::{
+        matrix::MatrixSession,
+        oauth::{
+            ClientId, OAuthSession, UserSession,
+            registration::{ApplicationType, ClientMetadata, Localized, OAuthGrantType},
+        },
+    },
+    ruma::{OwnedDeviceId, OwnedUserId, UserId, api::client::error::ErrorKind, serde::Raw},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, trace, warn};
+
+use crate::{
+    BotConfig, ShareableDatabase,
+    admin_table::{self, DEVICE_ID_ENTRY, SESSION_ENTRY},
+};
+
+/// Configuration of the OAuth login.
+#[derive(Deserialize)]
+pub struct OAuthConfig {
+    /// where the browser is redirected after authorizing the bot; this page doesn't need to
+    /// exist, its URL is pasted back to the bot.
+    #[serde(default = "default_redirect_uri")]
+    pub redirect_uri: String,
+    /// the bot's home page, shown when authorizing it.
+    #[serde(default = "default_client_uri")]
+    pub client_uri: String,
+}
+
+fn default_redirect_uri() -> String {
+    "http://localhost/callback".to_owned()
+}
+
+fn default_client_uri() -> String {
+    "https://github.com/bnjbvr/trinity".to_owned()
+}
+
+/// A session, as persisted in the database.
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    user_id: OwnedUserId,
+    device_id: OwnedDeviceId,
+    access_token: String,
+    refresh_token: Option<String>,
+    /// ID of the OAuth client, for sessions obtained through OAuth.
+    oauth_client_id: Option<String>,
+}
+
+impl StoredSession {
+    fn from_client(client: &Client) -> Option<Self> {
+        let (meta, tokens, oauth_client_id) = match client.session()? {
+            AuthSession::Matrix(session) => (session.meta, session.tokens, None),
+            AuthSession::OAuth(session) => (
+                session.user.meta,
+                session.user.tokens,
+                Some(session.client_id.as_str().to_owned()),
+            ),
+            _ => return None,
+        };
+        Some(Self {
+            user_id: meta.user_id,
+            device_id: meta.device_id,
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            oauth_client_id,
+        })
+    }
+
+    fn into_auth_session(self) -> AuthSession {
+        let meta = SessionMeta {
+            user_id: self.user_id,
+            device_id: self.device_id,
+        };
+        let tokens = SessionTokens {
+            access_token: self.access_token,
+            refresh_token: self.refresh_token,
+        };
+        match self.oauth_client_id {
+            Some(client_id) => AuthSession::OAuth(Box::new(OAuthSession {
+                client_id: ClientId::new(client_id),
+                user: UserSession { meta, tokens },
+            })),
+            None => AuthSession::Matrix(MatrixSession { meta, tokens }),
+        }
+    }
+}
+
+/// Logs the bot in, restoring the persisted session if there's one.
+pub(crate) async fn login(
+    client: &Client,
+    config: &BotConfig,
+    db: &ShareableDatabase,
+) -> anyhow::Result<()> {
+    // A pre-issued access token is always used as is.
+    if let Some(access_token) = &config.access_token {
+        let device_id = config
+            .device_id
+            .clone()
+            .context("a device_id is required along with the access_token")?;
+        // The homeserver's name may not be the server name of the user ID (e.g. with delegation),
+        // so a localpart-only user_id can't be completed.
+        let user_id = UserId::parse(config.user_id.as_str()).with_context(|| {
+            format!(
+                "the user_id must be a full Matrix ID (like @trinity:example.com) when using an \
+                 access_token, got {}",
+                config.user_id
+            )
+        })?;
+        debug!("using the configured access token...");
+        client
+            .restore_session(MatrixSession {
+                meta: SessionMeta {
+                    user_id,
+                    device_id: device_id.into(),
+                },
+                tokens: SessionTokens {
+                    access_token: access_token.clone(),
+                    refresh_token: None,
+                },
+            })
+            .await?;
+        return Ok(());
+    }
+
+    if restore_session(client, &config.user_id, db).await? {
+        return Ok(());
+    }
+
+    if let Some(oauth) = &config.oauth {
+        login_oauth(client, oauth).await?;
+    } else if let Some(password) = &config.password {
+        login_password(client, &config.user_id, password, db).await?;
+    } else {
+        anyhow::bail!("one of password, access_token or oauth must be configured to log in");
+    }
+
+    save_session(client, db)?;
+    Ok(())
+}
+
+/// Keeps the persisted session up to date when its tokens are refreshed, until the client is
+/// dropped.
+pub(crate) fn persist_session_changes(client: &Client, db: ShareableDatabase) {
+    let mut changes = client.subscribe_to_session_changes();
+    let client = client.clone();
+    tokio::spawn(async move {
+        while let Ok(change) = changes.recv().await {
+            match change {
+                SessionChange::TokensRefreshed => {
+                    trace!("access token refreshed");
+                    if let Err(err) = save_session(&client, &db) {
+                        error!("couldn't save the refreshed session: {err:#}");
+                    }
+                }
+                SessionChange::UnknownToken { soft_logout } => {
+                    warn!("the access token isn't valid anymore (soft logout: {soft_logout})");
+                }
+            }
+        }
+    });
+}
+
+/// Restores the session persisted in the database, if there's one, it belongs to the configured
+/// user, and it's still valid.
+///
+/// Returns whether a session has been restored.
+async fn restore_session(
+    client: &Client,
+    user_id: &str,
+    db: &ShareableDatabase,
+) -> anyhow::Result<bool> {
+    let Some(session) =
+        admin_table::read_str(db, SESSION_ENTRY).context("reading session from the database")?
+    else {
+        return Ok(false);
+    };
+
+    let session: StoredSession =
+        serde_json::from_str(&session).context("parsing the persisted session")?;
+
+    // The configured user may have changed since the session was persisted.
+    if !is_user(&session.user_id, user_id) {
+        warn!(
+            "the persisted session belongs to {}, not to {user_id}: logging in again (the matrix \
+            store may need to be removed too)",
+            session.user_id
+        );
+        admin_table::remove(db, SESSION_ENTRY)?;
+        admin_table::remove(db, DEVICE_ID_ENTRY)?;
+        return Ok(false);
+    }
+
+    debug!("restoring previous session...");
+    client.restore_session(session.into_auth_session()).await?;
+
+    // Make sure the session hasn't been invalidated in the meanwhile, e.g. by a logout.
+    match client.whoami().await {
+        Ok(_) => Ok(true),
+        Err(err)
+            if matches!(
+                err.client_api_error_kind(),
+                Some(ErrorKind::UnknownToken { .. })
+            ) =>
+        {
+            warn!("the persisted session isn't valid anymore, logging in again");
+            admin_table::remove(db, SESSION_ENTRY)?;
+            Ok(false)
+        }
+        Err(err) => {
+            // Likely a transient error: keep the session, the sync will retry.
+            warn!("couldn't check the persisted session: {err}");
+            Ok(true)
+        }
+    }
+}
+
+/// Whether the user ID matches the configured user, given as a full user ID or a localpart.
+fn is_user(user_id: &UserId, configured: &str) -> bool {
+    user_id.as_str() == configured || user_id.localpart() == configured
+}
+
+fn save_session(client: &Client, db: &ShareableDatabase) -> anyhow::Result<()> {
+    let session = StoredSession::from_client(client).context("missing session after login")?;
+    admin_table::write_str(db, SESSION_ENTRY, &serde_json::to_string(&session)?)
+        .context("writing session into the database")
+}
+
+async fn login_password(
+    client: &Client,
+    user_id: &str,
+    password: &str,
+    db: &ShareableDatabase,
+) -> anyhow::Result<()> {
+    debug!("logging in...");
+    let mut login_builder = client
+        .matrix_auth()
+        .login_username(user_id, password)
+        .request_refresh_token();
+
+    let mut db_device_id = None;
+    if let Some(device_id) =
+        admin_table::read_str(db, DEVICE_ID_ENTRY).context("reading device_id from the database")?
+    {
+        trace!("reusing previous device_id...");
+        // the login builder keeps a reference to the previous device id string, so can't clone
+        // db_device_id here, it has to outlive the login_builder.
+        db_device_id = Some(device_id);
+        login_builder = login_builder.device_id(db_device_id.as_ref().unwrap());
+    }
+
+    let resp = login_builder.send().await?;
+
+    let resp_device_id = resp.device_id.to_string();
+    if db_device_id.as_ref() != Some(&resp_device_id) {
+        match db_device_id {
+            Some(prev) => {
+                warn!("overriding device_id (previous was {prev}, new is {resp_device_id})")
+            }
+            None => debug!("storing new device_id for the first time..."),
+        }
+        admin_table::write_str(db, DEVICE_ID_ENTRY, &resp_device_id)
+            .context("writing new device_id into the database")?;
+    }
+
+    Ok(())
+}
+
+/// Logs in through OAuth, asking the admin to authorize the bot in a browser.
+async fn login_oauth(client: &Client, config: &OAuthConfig) -> anyhow::Result<()> {
+    let redirect_uri =
+        reqwest::Url::parse(&config.redirect_uri).context("invalid oauth redirect_uri")?;
+    let client_uri = reqwest::Url::parse(&config.client_uri).context("invalid oauth client_uri")?;
+
+    let mut metadata = ClientMetadata::new(
+        ApplicationType::Native,
+        vec![OAuthGrantType::AuthorizationCode {
+            redirect_uris: vec![redirect_uri.clone()],
+        }],
+        Localized::new(client_uri, None),
+    );
+    metadata.client_name = Some(Localized::new("Trinity".to_owned(), None));
+    let metadata = Raw::new(&metadata)?;
+
+    // The redirected URL is read from the terminal, which isn't available when running as a
+    // service.
+    anyhow::ensure!(
+        std::io::stdin().is_terminal(),
+        "logging in through OAuth requires a terminal, to paste the redirected URL: run the bot \
+        interactively once, so that the session is persisted, or use an access_token instead"
+    );
+
+    let oauth = client.oauth();
+    let authorization = oauth
+        .login(redirect_uri, None, Some(metadata.into()))
+        .build()
+        .await?;
+
+    info!(
+        "open this URL in a browser to authorize the bot, then paste here the URL of the page \
+        you're redirected to:\n{}",
+        authorization.url
+    );
+
+    let callback = tokio::task::spawn_blocking(|| {
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        anyhow::Ok(line)
+    })
+    .await??;
+    let callback = reqwest::Url::parse(callback.trim()).context("invalid redirected URL")?;
+
+    oauth.finish_login(callback.into()).await?;
+    info!("logged in through OAuth");
+    Ok(())
+}
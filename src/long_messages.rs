@@ -0,0 +1,544 @@
+//! Handling of the responses that are too long to be sent as a single message.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::{
+    html::{self, Mentioned},
+    wasm,
+};
+
+/// Default maximum length of a message, in bytes.
+pub const DEFAULT_MAX_MESSAGE_LENGTH: usize = 4096;
+
+/// Maximum length of the summary of a long message, in bytes.
+const SUMMARY_LENGTH: usize = 200;
+
+/// Summary of the `<details>` blocks following the first one, when a message is split.
+const CONTINUED_SUMMARY: &str = "(continued)";
+
+/// What to do with a response that's too long.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Strategy {
+    /// Split it into several messages, between paragraphs or code blocks when possible.
+    #[default]
+    Split,
+    /// Collapse it into a `<details>` block, showing only its first line.
+    Details,
+    /// Upload it as a file, along with a short summary message.
+    File,
+}
+
+pub(crate) struct LongMessages {
+    max_length: usize,
+    strategies: HashMap<String, Strategy>,
+}
+
+impl LongMessages {
+    pub fn new(max_length: usize, strategies: HashMap<String, Strategy>) -> Self {
+        Self {
+            max_length,
+            strategies,
+        }
+    }
+
+    /// Turns a response from the given module into the actions to send it, applying the module's
+    /// strategy if it's too long.
+    ///
+    /// `mentioned` are the users the message mentions, with their display names, which their
+    /// pills will show.
+    pub fn fit(
+        &self,
+        module: Option<&str>,
+        msg: wasm::Message,
+        mentioned: &[Mentioned],
+    ) -> Vec<wasm::Action> {
+        if length(&msg, mentioned) <= self.max_length {
+            return vec![wasm::Action::Respond(msg)];
+        }
+
+        let strategy = module
+            .and_then(|module| self.strategies.get(module).copied())
+            .unwrap_or_default();
+
+        match strategy {
+            Strategy::Split => self
+                .split(msg, mentioned)
+                .into_iter()
+                .map(wasm::Action::Respond)
+                .collect(),
+            Strategy::Details => self
+                .details(msg, mentioned)
+                .into_iter()
+                .map(wasm::Action::Respond)
+                .collect(),
+            Strategy::File => file(msg),
+        }
+    }
+
+    /// Splits a message into several ones that aren't too long.
+    ///
+    /// HTML messages are split as plain text, since their markup can't be split safely. Only the
+    /// first message mentions users.
+    pub fn split(&self, msg: wasm::Message, mentioned: &[Mentioned]) -> Vec<wasm::Message> {
+        if length(&msg, mentioned) <= self.max_length {
+            return vec![msg];
+        }
+
+        let format = match msg.format {
+            wasm::MessageFormat::Html(_) => wasm::MessageFormat::Plain,
+            format => format,
+        };
+
+        self.shrink(&msg.text, self.max_length, mentioned, |chunks| {
+            let mut mentions = msg.mentions.clone();
+            let mut mention_room = msg.mention_room;
+            chunks
+                .into_iter()
+                .map(|text| wasm::Message {
+                    text,
+                    format: format.clone(),
+                    to: msg.to.clone(),
+                    mentions: std::mem::take(&mut mentions),
+                    mention_room: std::mem::take(&mut mention_room),
+                })
+                .collect()
+        })
+    }
+
+    /// Collapses a message into a `<details>` block, summarized by its first line; if that's
+    /// still too long, the message is split into several blocks.
+    ///
+    /// HTML messages are split as plain text, like in [`Self::split`].
+    fn details(&self, msg: wasm::Message, mentioned: &[Mentioned]) -> Vec<wasm::Message> {
+        let first_line = msg.text.lines().find(|line| !line.trim().is_empty());
+        let summary = html::escape(&truncate(
+            first_line.unwrap_or_default().trim(),
+            SUMMARY_LENGTH,
+        ));
+
+        let collapsed = wasm::Message {
+            format: wasm::MessageFormat::Html(collapse(&summary, &render(&msg.format, &msg.text))),
+            ..msg.clone()
+        };
+        if length(&collapsed, mentioned) <= self.max_length {
+            return vec![collapsed];
+        }
+
+        let format = match msg.format {
+            wasm::MessageFormat::Html(_) => wasm::MessageFormat::Plain,
+            format => format,
+        };
+
+        let overhead = collapse(&summary, "")
+            .len()
+            .max(collapse(CONTINUED_SUMMARY, "").len());
+        let budget = self.max_length.saturating_sub(overhead);
+        self.shrink(&msg.text, budget, mentioned, |chunks| {
+            let mut mentions = msg.mentions.clone();
+            let mut mention_room = msg.mention_room;
+            chunks
+                .into_iter()
+                .enumerate()
+                .map(|(i, text)| wasm::Message {
+                    format: wasm::MessageFormat::Html(collapse(
+                        if i == 0 { &summary } else { CONTINUED_SUMMARY },
+                        &render(&format, &text),
+                    )),
+                    text,
+                    to: msg.to.clone(),
+                    mentions: std::mem::take(&mut mentions),
+                    mention_room: std::mem::take(&mut mention_room),
+                })
+                .collect()
+        })
+    }
+
+    /// Splits text into chunks of at most `budget` bytes, and turns them into messages with
+    /// `build`; since rendering the chunks can make them longer, they're shrunk until all the
+    /// messages fit.
+    fn shrink(
+        &self,
+        text: &str,
+        budget: usize,
+        mentioned: &[Mentioned],
+        mut build: impl FnMut(Vec<String>) -> Vec<wasm::Message>,
+    ) -> Vec<wasm::Message> {
+        let mut budget = budget.max(1);
+        loop {
+            let messages = build(split_text(text, budget));
+            let longest = messages
+                .iter()
+                .map(|msg| length(msg, mentioned))
+                .max()
+                .unwrap_or(0);
+            if longest <= self.max_length || budget == 1 {
+                return messages;
+            }
+            budget = (budget * self.max_length / longest).clamp(1, budget - 1);
+        }
+    }
+}
+
+/// Length of a message once sent, in bytes: the longest of its text and its HTML, with the pills
+/// of the users it mentions.
+fn length(msg: &wasm::Message, mentioned: &[Mentioned]) -> usize {
+    let mentioned: Vec<_> = mentioned
+        .iter()
+        .filter(|(user_id, _)| msg.mentions.iter().any(|id| id == user_id.as_str()))
+        .cloned()
+        .collect();
+    let (text, html) = html::render_message(msg, &mentioned);
+    text.len().max(html.map_or(0, |html| html.len()))
+}
+
+/// Renders the text of a message in the given format to HTML.
+fn render(format: &wasm::MessageFormat, text: &str) -> String {
+    match format {
+        wasm::MessageFormat::Plain => html::escape(text).replace('\n', "<br>"),
+        wasm::MessageFormat::Markdown => html::render_markdown(text),
+        wasm::MessageFormat::Html(html) => html.clone(),
+    }
+}
+
+/// Wraps HTML in a `<details>` block with the given, already escaped, summary.
+fn collapse(summary: &str, html: &str) -> String {
+    format!("<details><summary>{summary}</summary>{html}</details>")
+}
+
+/// Replaces a message with a short summary, and uploads its full content as a file.
+fn file(msg: wasm::Message) -> Vec<wasm::Action> {
+    let (filename, mimetype, data) = match &msg.format {
+        wasm::MessageFormat::Plain => ("response.txt", "text/plain", msg.text.clone()),
+        wasm::MessageFormat::Markdown => ("response.md", "text/markdown", msg.text.clone()),
+        wasm::MessageFormat::Html(html) => ("response.html", "text/html", html.clone()),
+    };
+
+    let first_paragraph = msg.text.trim().split("\n\n").next().unwrap_or_default();
+    let summary = truncate(first_paragraph, SUMMARY_LENGTH);
+
+    vec![
+        wasm::Action::Respond(wasm::Message {
+            text: format!("{summary}\n\n(The full response is attached.)"),
+            format: wasm::MessageFormat::Plain,
+            ..msg
+        }),
+        wasm::Action::Attach(wasm::Attachment {
+            filename: filename.to_owned(),
+            mimetype: mimetype.to_owned(),
+            data: data.into_bytes(),
+            width: None,
+            height: None,
+            thumbnail: None,
+        }),
+    ]
+}
+
+/// Truncates text to at most `max` bytes, adding an ellipsis if it was truncated.
+fn truncate(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_owned();
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+/// Splits text into chunks of at most `max` bytes, between paragraphs when possible, and keeping
+/// code blocks in a single chunk unless they're too long themselves.
+fn split_text(text: &str, max: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for block in blocks(text) {
+        for piece in split_block(&block, max) {
+            if !current.is_empty() && current.len() + 2 + piece.len() > max {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(&piece);
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Splits text into paragraphs and code blocks, which are separated by blank lines.
+fn blocks(text: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut in_code = false;
+
+    for line in text.lines() {
+        if !in_code && line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+    }
+
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+    blocks
+}
+
+/// Splits a single paragraph or code block into pieces of at most `max` bytes, between lines when
+/// possible; code blocks are closed and reopened around each piece.
+fn split_block(block: &str, max: usize) -> Vec<String> {
+    if block.len() <= max {
+        return vec![block.to_owned()];
+    }
+
+    let mut lines: Vec<&str> = block.lines().collect();
+    let is_code = lines.len() >= 2
+        && lines[0].trim_start().starts_with("```")
+        && lines[lines.len() - 1].trim_start().starts_with("```");
+    let fences = if is_code {
+        let close = lines.pop().unwrap();
+        let open = lines.remove(0);
+        Some((open, close))
+    } else {
+        None
+    };
+
+    let overhead = fences.map_or(0, |(open, close)| open.len() + close.len() + 2);
+    let max = max.saturating_sub(overhead).max(1);
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        for part in split_line(line, max) {
+            if !current.is_empty() && current.len() + 1 + part.len() > max {
+                pieces.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(part);
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+
+    match fences {
+        Some((open, close)) => pieces
+            .into_iter()
+            .map(|piece| format!("{open}\n{piece}\n{close}"))
+            .collect(),
+        None => pieces,
+    }
+}
+
+/// Splits a single line into parts of at most `max` bytes, at character boundaries.
+fn split_line(mut line: &str, max: usize) -> Vec<&str> {
+    let mut parts = Vec::new();
+    while line.len() > max {
+        let mut end = max;
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // A single character is longer than the limit: keep it whole.
+            end = line.chars().next().map_or(line.len(), char::len_utf8);
+        }
+        let (part, rest) = line.split_at(end);
+        parts.push(part);
+        line = rest;
+    }
+    parts.push(line);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: usize = 300;
+
+    fn mentioned() -> Vec<Mentioned> {
+        vec![(
+            "@user:example.org".try_into().unwrap(),
+            Some("A user with a rather long display name".to_owned()),
+        )]
+    }
+
+    fn message(text: String, format: wasm::MessageFormat) -> wasm::Message {
+        wasm::Message {
+            text,
+            format,
+            to: "@user:example.org".to_owned(),
+            mentions: vec!["@user:example.org".to_owned()],
+            mention_room: false,
+        }
+    }
+
+    fn long_text() -> String {
+        (0..40)
+            .map(|i| {
+                format!("Paragraph {i} for @user:example.org with <tags> & \"quotes\" to escape.")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn fit(strategy: Strategy, msg: wasm::Message) -> Vec<wasm::Action> {
+        let strategies = HashMap::from([("module".to_owned(), strategy)]);
+        LongMessages::new(MAX, strategies).fit(Some("module"), msg, &mentioned())
+    }
+
+    fn responses(actions: &[wasm::Action]) -> Vec<&wasm::Message> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                wasm::Action::Respond(msg) => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn short_messages_are_kept() {
+        let actions = fit(
+            Strategy::Details,
+            message("hello".to_owned(), wasm::MessageFormat::Plain),
+        );
+        let responses = responses(&actions);
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].text, "hello");
+        assert!(matches!(responses[0].format, wasm::MessageFormat::Plain));
+    }
+
+    #[test]
+    fn split_messages_fit() {
+        let text = long_text();
+        let actions = fit(
+            Strategy::Split,
+            message(text.clone(), wasm::MessageFormat::Markdown),
+        );
+        let responses = responses(&actions);
+        assert!(responses.len() > 1);
+        assert!(responses.iter().all(|msg| length(msg, &mentioned()) <= MAX));
+        assert_eq!(
+            responses
+                .iter()
+                .map(|msg| msg.text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            text
+        );
+        assert_eq!(responses[0].mentions.len(), 1);
+        assert!(responses[1..].iter().all(|msg| msg.mentions.is_empty()));
+    }
+
+    #[test]
+    fn split_messages_fit_with_their_pills() {
+        // The text fits, but not its HTML once every mention is turned into a pill.
+        let text = ["@user:example.org"; 12].join(" ");
+        assert!(text.len() <= MAX);
+        let actions = fit(Strategy::Split, message(text, wasm::MessageFormat::Plain));
+        let responses = responses(&actions);
+        assert!(responses.len() > 1);
+        let (_, html) = html::render_message(responses[0], &mentioned());
+        assert!(html.unwrap().len() <= MAX);
+    }
+
+    #[test]
+    fn split_code_blocks_are_reopened() {
+        let code = (0..100)
+            .map(|i| format!("let x{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text = format!("```rust\n{code}\n```");
+        let actions = fit(
+            Strategy::Split,
+            message(text, wasm::MessageFormat::Markdown),
+        );
+        let responses = responses(&actions);
+        assert!(responses.len() > 1);
+        for msg in responses {
+            assert!(length(msg, &mentioned()) <= MAX);
+            assert!(msg.text.starts_with("```rust\n"));
+            assert!(msg.text.ends_with("\n```"));
+        }
+    }
+
+    #[test]
+    fn details_fit() {
+        for format in [
+            wasm::MessageFormat::Plain,
+            wasm::MessageFormat::Markdown,
+            wasm::MessageFormat::Html(html::escape(&long_text())),
+        ] {
+            let actions = fit(Strategy::Details, message(long_text(), format));
+            let responses = responses(&actions);
+            assert!(responses.len() > 1);
+            for (i, msg) in responses.iter().enumerate() {
+                assert!(length(msg, &mentioned()) <= MAX, "message {i} is too long");
+                let wasm::MessageFormat::Html(html) = &msg.format else {
+                    panic!("message {i} isn't collapsed");
+                };
+                assert!(html.starts_with("<details><summary>"));
+                assert!(html.ends_with("</details>"));
+            }
+        }
+    }
+
+    #[test]
+    fn details_without_splitting() {
+        let strategies = HashMap::from([("module".to_owned(), Strategy::Details)]);
+        let long_messages = LongMessages::new(MAX, strategies);
+        let text = "First line\nsecond line".to_owned();
+        let responses =
+            long_messages.details(message(text.clone(), wasm::MessageFormat::Plain), &[]);
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].text, text);
+        let wasm::MessageFormat::Html(html) = &responses[0].format else {
+            panic!("the message isn't collapsed");
+        };
+        assert_eq!(
+            html,
+            "<details><summary>First line</summary>First line<br>second line</details>"
+        );
+    }
+
+    #[test]
+    fn file_keeps_the_full_content() {
+        let text = long_text();
+        let actions = fit(
+            Strategy::File,
+            message(text.clone(), wasm::MessageFormat::Plain),
+        );
+        let responses = responses(&actions);
+        assert_eq!(responses.len(), 1);
+        assert!(length(responses[0], &mentioned()) <= MAX);
+        let attachments: Vec<_> = actions
+            .iter()
+            .filter_map(|action| match action {
+                wasm::Action::Attach(attachment) => Some(attachment),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].data, text.into_bytes());
+        assert_eq!(attachments[0].mimetype, "text/plain");
+    }
+}
@@ -0,0 +1,157 @@
+//! Protections against the bot talking to other bots, or to itself, forever.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use matrix_sdk::ruma::{OwnedRoomId, OwnedUserId, RoomId, UserId};
+use serde::Deserialize;
+
+/// Configuration of the loop protections.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct LoopProtectionConfig {
+    /// users whose messages are always ignored, e.g. other bots.
+    pub ignored_users: Vec<OwnedUserId>,
+    /// servers whose users' messages are always ignored; `*` matches any sequence of characters,
+    /// e.g. `*.bots.example.com`.
+    pub ignored_servers: Vec<String>,
+    /// whether to ignore `m.notice` messages, which are conventionally sent by bots.
+    pub ignore_notices: bool,
+    /// users known to be bots, whose messages are handled but may be part of a loop, even when
+    /// they reply to the bot.
+    pub bots: Vec<OwnedUserId>,
+    /// how many messages the bot can respond to in a room within `window_secs`, without any
+    /// message it didn't respond to in between, before the room is paused.
+    pub max_bot_messages: usize,
+    /// duration of the window in which the bot's responses are counted, in seconds.
+    pub window_secs: u64,
+    /// how long a room stays paused, in seconds.
+    pub pause_secs: u64,
+}
+
+impl Default for LoopProtectionConfig {
+    fn default() -> Self {
+        Self {
+            ignored_users: Vec::new(),
+            ignored_servers: Vec::new(),
+            ignore_notices: true,
+            bots: Vec::new(),
+            max_bot_messages: 10,
+            window_secs: 60,
+            pause_secs: 300,
+        }
+    }
+}
+
+/// Bot activity in a single room.
+#[derive(Default)]
+struct RoomActivity {
+    /// When the bot responded to messages, since the last message it didn't respond to.
+    responded: VecDeque<Instant>,
+    /// When the room will be resumed, if it's paused.
+    paused_until: Option<Instant>,
+}
+
+/// Decides which messages the bot should ignore, so it doesn't end up in an endless conversation
+/// with another bot.
+///
+/// A loop is detected when the bot responds to too many messages in a short time, while responding
+/// to every single message: any message it doesn't respond to is assumed to come from a human, and
+/// resets the count. Replies to the bot from users who aren't known bots are part of a
+/// conversation with a human, and aren't counted.
+pub(crate) struct LoopProtection {
+    config: LoopProtectionConfig,
+    ignored_servers: Vec<regex::Regex>,
+    rooms: HashMap<OwnedRoomId, RoomActivity>,
+}
+
+impl LoopProtection {
+    pub fn new(config: LoopProtectionConfig) -> Self {
+        let ignored_servers = config
+            .ignored_servers
+            .iter()
+            .map(|pattern| {
+                let pattern = regex::escape(pattern).replace(r"\*", ".*");
+                regex::Regex::new(&format!("^{pattern}$")).expect("escaped patterns are valid")
+            })
+            .collect();
+
+        Self {
+            config,
+            ignored_servers,
+            rooms: HashMap::new(),
+        }
+    }
+
+    /// Whether the notices (`m.notice` messages) should be ignored.
+    pub fn ignore_notices(&self) -> bool {
+        self.config.ignore_notices
+    }
+
+    /// Whether messages from this user should be ignored.
+    pub fn is_ignored(&self, user_id: &UserId) -> bool {
+        self.config.ignored_users.iter().any(|id| id == user_id)
+            || self
+                .ignored_servers
+                .iter()
+                .any(|pattern| pattern.is_match(user_id.server_name().as_str()))
+    }
+
+    /// Whether this user is known to be a bot.
+    pub fn is_bot(&self, user_id: &UserId) -> bool {
+        self.config.bots.iter().any(|id| id == user_id)
+    }
+
+    /// Whether the room is paused, because a loop was detected in it recently.
+    pub fn is_paused(&mut self, room: &RoomId) -> bool {
+        let Some(activity) = self.rooms.get_mut(room) else {
+            return false;
+        };
+        match activity.paused_until {
+            Some(until) if Instant::now() < until => true,
+            Some(_) => {
+                activity.paused_until = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Records that the bot handled a message in the room, and whether it `responded` to it;
+    /// `counted` tells whether the message may be part of a loop, i.e. it isn't a reply to the
+    /// bot from a user who isn't a known bot.
+    ///
+    /// Returns true if the room has just been paused, as a result.
+    pub fn on_handled(&mut self, room: &RoomId, responded: bool, counted: bool) -> bool {
+        if !responded {
+            self.rooms.remove(room);
+            return false;
+        }
+        if !counted {
+            return false;
+        }
+
+        let now = Instant::now();
+        let window = Duration::from_secs(self.config.window_secs);
+
+        let activity = self.rooms.entry(room.to_owned()).or_default();
+        activity.responded.push_back(now);
+        while activity
+            .responded
+            .front()
+            .is_some_and(|sent_at| now.duration_since(*sent_at) > window)
+        {
+            activity.responded.pop_front();
+        }
+
+        if activity.responded.len() <= self.config.max_bot_messages {
+            return false;
+        }
+
+        activity.responded.clear();
+        activity.paused_until = Some(now + Duration::from_secs(self.config.pause_secs));
+        true
+    }
+}
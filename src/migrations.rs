@@ -0,0 +1,151 @@
+//! Data migrations, for the host database and for the modules' stored data.
+//!
+//! Host migrations are declared in [`MIGRATIONS`], in order of application; the number of
+//! migrations that already ran is stored under [`admin_table::VERSION_ENTRY`]. Modules declare
+//! their own migrations through the `migrations` export, and their progress is tracked in the
+//! admin table too, under a per-module key.
+
+use anyhow::Context as _;
+use std::fs;
+use tracing::{debug, info, warn};
+
+use crate::{BotConfig, ShareableDatabase, admin_table};
+
+/// A single host data migration.
+pub(crate) struct Migration {
+    /// Human readable name of the migration, used for logging.
+    pub name: &'static str,
+
+    /// Runs the migration.
+    ///
+    /// Database changes must go through the given transaction, so that they're committed
+    /// atomically with the version bump.
+    pub up: fn(&BotConfig, &redb::WriteTransaction<'_>) -> anyhow::Result<()>,
+}
+
+/// All the host migrations, in order of application.
+///
+/// Never remove or reorder entries in this list: the index of a migration (plus one) is the
+/// version the database will be at, once it's applied.
+const MIGRATIONS: &[Migration] = &[Migration {
+    name: "drop matrix store and device id after the sdk bump",
+    up: drop_matrix_store,
+}];
+
+fn drop_matrix_store(config: &BotConfig, txn: &redb::WriteTransaction<'_>) -> anyhow::Result<()> {
+    // If no version is set, we assume this is the first run, which happens after the sdk bump.
+    // In this case, the state is likely outdated in the database, so we remove the database
+    // directory first, and then drop the device id, so as to create a new one.
+    if let Err(err) = fs::remove_dir_all(&config.matrix_store_path) {
+        warn!("failed to remove old database directory: {err:#}");
+    }
+
+    // Drop the device id, so that a new one is created on the next login.
+    admin_table::remove_in(txn, admin_table::DEVICE_ID_ENTRY)
+        .context("dropping device_id in the database")?;
+
+    Ok(())
+}
+
+/// Runs all the pending host migrations.
+///
+/// Each migration is applied in its own transaction, along with the version bump, so a failing
+/// migration leaves the database at the previous version. In dry-run mode, pending migrations
+/// are only logged.
+pub(crate) fn run(config: &BotConfig, db: &ShareableDatabase, dry_run: bool) -> anyhow::Result<()> {
+    let version = admin_table::read_u64(db, admin_table::VERSION_ENTRY)
+        .context("reading version from the database")?
+        .unwrap_or(0);
+
+    let pending = pending(version, MIGRATIONS.len())?;
+    if pending.is_empty() {
+        debug!("database is up to date (version {version})");
+        return Ok(());
+    }
+
+    for index in pending {
+        let migration = &MIGRATIONS[index];
+        let next_version = index as u64 + 1;
+
+        if dry_run {
+            info!(
+                "would run data migration {next_version}: {}",
+                migration.name
+            );
+            continue;
+        }
+
+        info!("running data migration {next_version}: {}", migration.name);
+
+        let txn = db.begin_write()?;
+        if let Err(err) = (migration.up)(config, &txn) {
+            txn.abort()?;
+            return Err(err.context(format!("data migration {next_version} failed")));
+        }
+        admin_table::write_u64_in(&txn, admin_table::VERSION_ENTRY, next_version)
+            .context("writing new version into the database")?;
+        txn.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Runs all the pending migrations of a single module, calling `migrate` for each of them.
+///
+/// `declared` is the list of migration names the module declared, in order. The number of
+/// migrations that ran is persisted after each successful step, so a failure can be retried on
+/// the next load. Module migrations write through the key-value API, in their own transactions,
+/// so a migration can run again if the bot stops before its completion is persisted; they're
+/// required to be idempotent.
+pub(crate) fn run_module(
+    db: &ShareableDatabase,
+    module_name: &str,
+    declared: &[String],
+    dry_run: bool,
+    mut migrate: impl FnMut(&str) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let key = module_version_key(module_name);
+    let version = module_version(db, module_name)?;
+
+    for index in pending(version, declared.len())? {
+        let name = &declared[index];
+        let next_version = index as u64 + 1;
+
+        if dry_run {
+            info!("would run migration {next_version} of module {module_name}: {name}");
+            continue;
+        }
+
+        info!("running migration {next_version} of module {module_name}: {name}");
+        migrate(name)
+            .with_context(|| format!("migration {name} of module {module_name} failed"))?;
+
+        admin_table::write_u64(db, &key, next_version)
+            .with_context(|| format!("writing new version of module {module_name}"))?;
+    }
+
+    Ok(())
+}
+
+/// Returns the number of migrations of the module that already ran.
+pub(crate) fn module_version(db: &ShareableDatabase, module_name: &str) -> anyhow::Result<u64> {
+    Ok(admin_table::read_u64(db, &module_version_key(module_name))
+        .with_context(|| format!("reading version of module {module_name}"))?
+        .unwrap_or(0))
+}
+
+/// Returns the indexes of the migrations that still need to run, given the current version and
+/// the number of known migrations.
+fn pending(version: u64, num_migrations: usize) -> anyhow::Result<std::ops::Range<usize>> {
+    let version = version as usize;
+    anyhow::ensure!(
+        version <= num_migrations,
+        "database is at version {version}, but only {num_migrations} migrations are known; was \
+         it written by a more recent version?"
+    );
+    Ok(version..num_migrations)
+}
+
+fn module_version_key(module_name: &str) -> String {
+    format!("{}{module_name}", admin_table::MODULE_VERSION_PREFIX)
+}
@@ -0,0 +1,288 @@
+//! Persistent queue of the events sent by the bot, so that they're not lost when sending fails.
+//!
+//! Events are stored in the database before being sent, and sent in order for each room. Sending
+//! is retried on transient failures, waiting for as long as the server asks when rate-limited, or
+//! with an exponential backoff otherwise; events that still can't be sent after a few attempts
+//! are moved to a dead-letter table.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context as _;
+use matrix_sdk::{
+    Client,
+    room::Room,
+    ruma::{
+        OwnedEventId, OwnedRoomId, TransactionId,
+        api::client::error::{ErrorKind, RetryAfter},
+        events::MessageLikeEventContent,
+    },
+};
+use redb::ReadableTable as _;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{debug, error, warn};
+
+use crate::ShareableDatabase;
+
+/// Maps a room ID to the list of events waiting to be sent in it, in order.
+const OUTBOX_TABLE: redb::TableDefinition<str, [u8]> = redb::TableDefinition::new("@outbox");
+
+/// Maps a transaction ID to an event that couldn't be sent.
+const DEAD_LETTERS_TABLE: redb::TableDefinition<str, [u8]> =
+    redb::TableDefinition::new("@outbox_dead_letters");
+
+/// How many times sending an event is attempted, before giving up.
+const MAX_ATTEMPTS: u32 = 8;
+
+/// Delay before the first retry, doubled after each failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay between two attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// An event waiting to be sent.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// Transaction ID of the event, reused across attempts so the server can deduplicate it.
+    transaction_id: String,
+    event_type: String,
+    content: serde_json::Value,
+    /// Number of failed attempts to send the event so far.
+    attempts: u32,
+}
+
+/// An event that couldn't be sent.
+#[derive(Serialize)]
+struct DeadLetter<'a> {
+    room_id: &'a str,
+    event_type: &'a str,
+    content: &'a serde_json::Value,
+    attempts: u32,
+    error: String,
+}
+
+pub(crate) struct Outbox {
+    db: ShareableDatabase,
+    /// Locks making sure the events of a given room are sent one at a time, in order.
+    rooms: std::sync::Mutex<HashMap<OwnedRoomId, Arc<Mutex<()>>>>,
+}
+
+impl Outbox {
+    pub fn new(db: ShareableDatabase) -> Self {
+        Self {
+            db,
+            rooms: Default::default(),
+        }
+    }
+
+    /// Queues an event to be sent in the room, after the events already queued for it, and waits
+    /// for it to be sent.
+    ///
+    /// Returns the ID of the sent event, or an error if it couldn't be sent at all.
+    pub async fn send(
+        &self,
+        room: &Room,
+        content: impl MessageLikeEventContent,
+    ) -> anyhow::Result<OwnedEventId> {
+        let transaction_id = TransactionId::new().to_string();
+        let entry = Entry {
+            transaction_id: transaction_id.clone(),
+            event_type: content.event_type().to_string(),
+            content: serde_json::to_value(&content)?,
+            attempts: 0,
+        };
+
+        let lock = self.room_lock(room);
+        let _guard = lock.lock().await;
+
+        let mut pending = self.pending(room)?;
+        pending.push(entry);
+        self.set_pending(room, &pending)?;
+
+        self.flush(room, Some(&transaction_id))
+            .await
+            .context("event isn't in the outbox anymore")?
+    }
+
+    /// Sends the events left in the outbox of every joined room, e.g. because the bot stopped
+    /// before they could be sent.
+    pub fn resume(self: &Arc<Self>, client: &Client) {
+        for room in client.joined_rooms() {
+            let outbox = self.clone();
+            tokio::spawn(async move {
+                let lock = outbox.room_lock(&room);
+                let _guard = lock.lock().await;
+                outbox.flush(&room, None).await;
+            });
+        }
+    }
+
+    fn room_lock(&self, room: &Room) -> Arc<Mutex<()>> {
+        self.rooms
+            .lock()
+            .unwrap()
+            .entry(room.room_id().to_owned())
+            .or_default()
+            .clone()
+    }
+
+    /// Sends all the events queued for the room, in order.
+    ///
+    /// Returns the outcome of sending the event with the given transaction ID, if any.
+    async fn flush(
+        &self,
+        room: &Room,
+        transaction_id: Option<&str>,
+    ) -> Option<anyhow::Result<OwnedEventId>> {
+        let mut result = None;
+
+        loop {
+            let mut pending = match self.pending(room) {
+                Ok(pending) => pending,
+                Err(err) => {
+                    error!("couldn't read the outbox of {}: {err:#}", room.room_id());
+                    return result;
+                }
+            };
+            if pending.is_empty() {
+                return result;
+            }
+
+            let entry = &mut pending[0];
+            let sent = room
+                .send_raw(&entry.event_type, entry.content.clone())
+                .with_transaction_id(<&TransactionId>::from(entry.transaction_id.as_str()))
+                .await;
+
+            let outcome = match sent {
+                Ok(sent) => Some(Ok(sent.event_id)),
+                Err(err) => {
+                    entry.attempts += 1;
+                    match retry_delay(&err, entry.attempts) {
+                        Some(delay) if entry.attempts < MAX_ATTEMPTS => {
+                            debug!(
+                                "couldn't send event in {}, retrying in {delay:?}: {err}",
+                                room.room_id()
+                            );
+                            if let Err(err) = self.set_pending(room, &pending) {
+                                warn!("couldn't update the outbox: {err:#}");
+                            }
+                            tokio::time::sleep(delay).await;
+                            None
+                        }
+                        _ => {
+                            self.dead_letter(room, entry, &err);
+                            Some(Err(anyhow::Error::from(err)))
+                        }
+                    }
+                }
+            };
+
+            // The event has been sent, or given up on: remove it from the outbox.
+            if let Some(outcome) = outcome {
+                let entry = pending.remove(0);
+                if transaction_id == Some(entry.transaction_id.as_str()) {
+                    result = Some(outcome);
+                }
+                if let Err(err) = self.set_pending(room, &pending) {
+                    error!("couldn't update the outbox: {err:#}");
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Records an event that couldn't be sent in the dead-letter table.
+    fn dead_letter(&self, room: &Room, entry: &Entry, err: &matrix_sdk::Error) {
+        error!(
+            "giving up on sending a {} event in {} after {} attempt(s): {err}",
+            entry.event_type,
+            room.room_id(),
+            entry.attempts
+        );
+
+        let letter = DeadLetter {
+            room_id: room.room_id().as_str(),
+            event_type: &entry.event_type,
+            content: &entry.content,
+            attempts: entry.attempts,
+            error: err.to_string(),
+        };
+
+        let result = (|| {
+            let letter = serde_json::to_vec(&letter)?;
+            let txn = self.db.begin_write()?;
+            {
+                let mut table = txn.open_table(DEAD_LETTERS_TABLE)?;
+                table.insert(&entry.transaction_id, &letter)?;
+            }
+            txn.commit()?;
+            anyhow::Ok(())
+        })();
+
+        if let Err(err) = result {
+            error!("couldn't record dead letter: {err:#}");
+        }
+    }
+
+    /// Returns the events waiting to be sent in the room.
+    fn pending(&self, room: &Room) -> anyhow::Result<Vec<Entry>> {
+        let txn = self.db.begin_read()?;
+        let table = match txn.open_table(OUTBOX_TABLE) {
+            Ok(table) => table,
+            Err(redb::Error::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(err) => Err(err)?,
+        };
+        match table.get(room.room_id().as_str())? {
+            Some(pending) => Ok(serde_json::from_slice(pending)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn set_pending(&self, room: &Room, pending: &[Entry]) -> anyhow::Result<()> {
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(OUTBOX_TABLE)?;
+            if pending.is_empty() {
+                table.remove(room.room_id().as_str())?;
+            } else {
+                table.insert(room.room_id().as_str(), &serde_json::to_vec(pending)?)?;
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+}
+
+/// How long to wait before trying to send an event again, after `attempts` failed attempts, or
+/// `None` if it's not worth retrying.
+fn retry_delay(err: &matrix_sdk::Error, attempts: u32) -> Option<Duration> {
+    let backoff = INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempts - 1))
+        .min(MAX_BACKOFF);
+
+    if let Some(ErrorKind::LimitExceeded { retry_after }) = err.client_api_error_kind() {
+        return Some(match retry_after {
+            Some(RetryAfter::Delay(delay)) => *delay,
+            Some(RetryAfter::DateTime(time)) => {
+                time.duration_since(SystemTime::now()).unwrap_or_default()
+            }
+            None => backoff,
+        });
+    }
+
+    // Other client errors, e.g. a missing permission, won't go away by retrying.
+    if err
+        .as_client_api_error()
+        .is_some_and(|err| err.status_code.is_client_error())
+    {
+        return None;
+    }
+
+    // Server errors and network failures are likely transient.
+    Some(backoff)
+}
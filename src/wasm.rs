@@ -5,8 +5,15 @@ mod module {
 }
 
 use crate::wasm::module::exports::trinity::module::messaging;
+use anyhow::Context as _;
 pub(crate) use messaging::Action;
+pub(crate) use messaging::Attachment;
+pub(crate) use messaging::Capability;
+pub(crate) use messaging::InboundMessageV1 as InboundMessage;
 pub(crate) use messaging::Message;
+pub(crate) use messaging::MessageFormat;
+pub(crate) use messaging::ModuleManifest;
+use messaging::Trigger;
 use module::TrinityModule;
 use rayon::iter::IntoParallelIterator as _;
 use rayon::iter::ParallelIterator as _;
@@ -14,19 +21,66 @@ use wasmtime::Store;
 
 mod apis;
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{DefaultHasher, Hash as _, Hasher as _};
+use std::path::{Path, PathBuf};
 
-use matrix_sdk::ruma::{RoomId, UserId};
+use matrix_sdk::{
+    Client,
+    ruma::{EventId, RoomId, UserId},
+};
+use serde::Deserialize;
 
-use crate::{ShareableDatabase, wasm::apis::Apis};
+use crate::{
+    ShareableDatabase,
+    wasm::apis::{Apis, RoomContext},
+};
 
 pub struct ModuleState {
     apis: Apis,
 }
 
+/// A permission an admin can grant to a module, in the configuration.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Permission {
+    /// Read the history of the room in which a message is handled.
+    ReadHistory,
+    /// Change the topic, name, pinned events and other state of the room in which a message is
+    /// handled.
+    WriteState,
+}
+
+/// Host resources the modules' APIs get access to.
+#[derive(Clone)]
+pub(crate) struct HostContext {
+    pub db: ShareableDatabase,
+    /// The Matrix client; missing when running the migrations in dry-run mode.
+    pub client: Option<Client>,
+    /// Permissions granted to each module, by module name.
+    pub permissions: HashMap<String, Vec<Permission>>,
+}
+
+impl HostContext {
+    fn permissions(&self, module_name: &str) -> &[Permission] {
+        self.permissions
+            .get(module_name)
+            .map_or(&[], |permissions| permissions.as_slice())
+    }
+}
+
 pub(crate) struct Module {
     name: String,
+    /// Path of the wasm file this module has been loaded from.
+    path: PathBuf,
+    /// Hash of the wasm file's content, to detect actual changes on hot reload.
+    hash: u64,
+    manifest: ModuleManifest,
+    /// Compiled version of the triggers declared in the manifest.
+    triggers: Vec<CompiledTrigger>,
+    /// Configuration the module has been initialized with, serialized as JSON.
+    config: Option<String>,
     instance: TrinityModule,
     pub store: Store<ModuleState>,
 }
@@ -36,6 +90,38 @@ impl Module {
         self.name.as_str()
     }
 
+    pub fn manifest(&self) -> &ModuleManifest {
+        &self.manifest
+    }
+
+    /// Whether the given message matches one of the module's triggers.
+    pub fn wants(&self, content: &str) -> bool {
+        self.triggers.iter().any(|trigger| match trigger {
+            CompiledTrigger::Prefix(prefix) => content.starts_with(prefix.as_str()),
+            CompiledTrigger::Regex(regex) => regex.is_match(content),
+            CompiledTrigger::All => true,
+        })
+    }
+
+    /// Initialize the module with its configuration.
+    fn init(&mut self) -> anyhow::Result<()> {
+        self.instance
+            .trinity_module_messaging()
+            .call_init(&mut self.store, self.config.as_deref())?
+            .map_err(|err| anyhow::anyhow!("init failed: {err}"))
+    }
+
+    /// Let the module know it's about to be unloaded.
+    pub fn shutdown(&mut self) {
+        if let Err(err) = self
+            .instance
+            .trinity_module_messaging()
+            .call_shutdown(&mut self.store)
+        {
+            tracing::error!("error when shutting down module {}: {err:#}", self.name);
+        }
+    }
+
     pub fn help(&mut self, topic: Option<&str>) -> anyhow::Result<String> {
         self.instance
             .trinity_module_messaging()
@@ -58,17 +144,112 @@ impl Module {
 
     pub fn handle(
         &mut self,
-        content: &str,
-        sender: &UserId,
+        message: &InboundMessage,
         room: &RoomId,
+        thread_root: Option<&EventId>,
     ) -> anyhow::Result<Vec<messaging::Action>> {
-        self.instance.trinity_module_messaging().call_on_msg(
-            &mut self.store,
-            content,
-            sender.as_str(),
-            "author name NYI",
-            room.as_str(),
-        )
+        self.in_room(room, thread_root, |instance, store| {
+            instance
+                .trinity_module_messaging()
+                .call_on_msg(store, message)
+        })
+    }
+
+    /// Handle a reply to the module's own message `in_reply_to`.
+    pub fn reply(
+        &mut self,
+        message: &InboundMessage,
+        in_reply_to: &str,
+        room: &RoomId,
+        thread_root: Option<&EventId>,
+    ) -> anyhow::Result<Vec<messaging::Action>> {
+        self.in_room(room, thread_root, |instance, store| {
+            instance
+                .trinity_module_messaging()
+                .call_on_reply(store, message, in_reply_to)
+        })
+    }
+
+    /// Handle an edit of the message `original`, which the module responded to.
+    pub fn edit(
+        &mut self,
+        message: &InboundMessage,
+        original: &str,
+        room: &RoomId,
+        thread_root: Option<&EventId>,
+    ) -> anyhow::Result<Vec<messaging::Action>> {
+        self.in_room(room, thread_root, |instance, store| {
+            instance
+                .trinity_module_messaging()
+                .call_on_edit(store, message, original)
+        })
+    }
+
+    /// Calls into the module, letting the host APIs know in which room it's handling a message.
+    fn in_room<T>(
+        &mut self,
+        room: &RoomId,
+        thread_root: Option<&EventId>,
+        call: impl FnOnce(&TrinityModule, &mut Store<ModuleState>) -> T,
+    ) -> T {
+        self.store.data_mut().apis.set_room(Some(RoomContext {
+            room: room.to_owned(),
+            thread_root: thread_root.map(ToOwned::to_owned),
+        }));
+
+        let result = call(&self.instance, &mut self.store);
+
+        self.store.data_mut().apis.set_room(None);
+        result
+    }
+}
+
+/// A [`Trigger`] ready to be matched against incoming messages.
+enum CompiledTrigger {
+    Prefix(String),
+    Regex(regex::Regex),
+    All,
+}
+
+impl CompiledTrigger {
+    fn new(trigger: &Trigger) -> anyhow::Result<Self> {
+        Ok(match trigger {
+            Trigger::Prefix(prefix) => Self::Prefix(prefix.clone()),
+            Trigger::Regex(regex) => Self::Regex(
+                regex::Regex::new(regex)
+                    .with_context(|| format!("invalid trigger regex {regex}"))?,
+            ),
+            Trigger::All => Self::All,
+        })
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Capability::Kv => "kv",
+            Capability::Log => "log",
+            Capability::SyncRequest => "sync-request",
+            Capability::Sys => "sys",
+            Capability::History => "history",
+            Capability::RoomInfo => "room-info",
+            Capability::RoomState => "room-state",
+            Capability::Typing => "typing",
+        })
+    }
+}
+
+/// A module that couldn't be loaded.
+pub(crate) struct LoadFailure {
+    /// Name of the module, or path of the modules directory that couldn't be read.
+    pub name: String,
+    /// Reason of the failure.
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for LoadFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:#}", self.name, self.error)
     }
 }
 
@@ -79,15 +260,19 @@ pub(crate) struct WasmModules {
 
 impl WasmModules {
     /// Create a new collection of wasm modules.
+    ///
+    /// A module or a modules directory failing to load doesn't prevent the other ones from
+    /// loading: it's skipped, and reported in the returned list of failures.
     pub fn new(
         engine: &wasmtime::Engine,
-        db: ShareableDatabase,
+        host: &HostContext,
         modules_paths: &[PathBuf],
-        modules_config: &HashMap<String, HashMap<String, String>>,
-    ) -> anyhow::Result<Self> {
+        modules_config: &HashMap<String, serde_json::Value>,
+    ) -> (Self, Vec<LoadFailure>) {
         tracing::debug!("setting up wasm context...");
 
         let mut compiled_modules = Vec::new();
+        let mut failures = Vec::new();
 
         tracing::debug!("precompiling wasm modules...");
         for modules_path in modules_paths {
@@ -96,80 +281,548 @@ impl WasmModules {
                 modules_path.to_string_lossy()
             );
 
-            // Collect all the modules paths and names.
-            let mut path_and_names = vec![];
-            for module_path in std::fs::read_dir(modules_path)? {
-                let module_path = module_path?.path();
-
-                if module_path.extension().map_or(true, |ext| ext != "wasm") {
+            let path_and_names = match list_modules(modules_path) {
+                Ok(path_and_names) => path_and_names,
+                Err(err) => {
+                    tracing::error!(
+                        "couldn't list modules in {}: {err:#}",
+                        modules_path.to_string_lossy()
+                    );
+                    failures.push(LoadFailure {
+                        name: modules_path.to_string_lossy().into_owned(),
+                        error: err.context("couldn't list the modules"),
+                    });
                     continue;
                 }
-
-                let name = module_path
-                    .file_stem()
-                    .map(|s| s.to_string_lossy())
-                    .unwrap_or_else(|| module_path.to_string_lossy())
-                    .to_string();
-
-                path_and_names.push((module_path, name));
-            }
+            };
 
             // Compile and re-init all the modules in parallel.
             let batch: Vec<_> = path_and_names
                 .into_par_iter()
-                .map(|(module_path, name)| -> anyhow::Result<Module> {
-                    let span = tracing::debug_span!("compiling module", name = %name, );
-                    let _scope = span.enter();
+                .map(|(module_path, name)| {
+                    std::fs::read(&module_path)
+                        .map_err(anyhow::Error::from)
+                        .and_then(|bytes| {
+                            load_module(
+                                engine,
+                                host,
+                                module_path,
+                                name.clone(),
+                                &bytes,
+                                modules_config,
+                            )
+                        })
+                        .map_err(|error| LoadFailure { name, error })
+                })
+                .collect();
+
+            for result in batch {
+                match result {
+                    Ok(module) => compiled_modules.push(module),
+                    Err(failure) => {
+                        tracing::error!("couldn't load module {failure}");
+                        failures.push(failure);
+                    }
+                }
+            }
+        }
+
+        (
+            Self {
+                modules: compiled_modules,
+            },
+            failures,
+        )
+    }
+
+    /// Reload the modules affected by changes to the given file paths.
+    ///
+    /// A module whose file was created or whose content changed is (re)compiled and
+    /// (re)initialized; a module whose file was removed is dropped. Other modules are left
+    /// untouched, and keep their in-memory state.
+    ///
+    /// If a module fails to load, its previous version (if any) is kept, and the failure is
+    /// reported in the returned list.
+    pub fn reload(
+        &mut self,
+        engine: &wasmtime::Engine,
+        host: &HostContext,
+        changed_paths: impl IntoIterator<Item = PathBuf>,
+        modules_paths: &[PathBuf],
+        modules_config: &HashMap<String, serde_json::Value>,
+    ) -> Vec<LoadFailure> {
+        let mut failures = Vec::new();
 
-                    tracing::debug!(
-                        path = module_path.to_str().unwrap_or("<invalid path>"),
-                        "initializing: creating APIs"
+        let modules_dirs: Vec<_> = modules_paths
+            .iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .collect();
+
+        for path in changed_paths {
+            let path = normalize_path(&path);
+
+            // Only consider modules living at the top-level of a modules directory, like
+            // `list_modules` does.
+            if !path
+                .parent()
+                .is_some_and(|parent| modules_dirs.iter().any(|dir| dir == parent))
+            {
+                continue;
+            }
+
+            if let Some(failure) = self.reload_path(engine, host, path, modules_config, false) {
+                failures.push(failure);
+            }
+        }
+
+        failures
+    }
+
+    /// Apply a new configuration to the modules.
+    ///
+    /// Modules living in a directory that's not part of `modules_paths` anymore are dropped, the
+    /// ones in new directories are loaded, and the ones listed in `reconfigured` are
+    /// reinitialized with their new configuration, even if their file didn't change.
+    pub fn reconfigure(
+        &mut self,
+        engine: &wasmtime::Engine,
+        host: &HostContext,
+        modules_paths: &[PathBuf],
+        modules_config: &HashMap<String, serde_json::Value>,
+        reconfigured: &HashSet<String>,
+    ) -> Vec<LoadFailure> {
+        let mut failures = Vec::new();
+
+        let modules_dirs: Vec<_> = modules_paths
+            .iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .collect();
+
+        let (kept, removed) =
+            std::mem::take(&mut self.modules)
+                .into_iter()
+                .partition(|module: &Module| {
+                    module
+                        .path
+                        .parent()
+                        .is_some_and(|parent| modules_dirs.iter().any(|dir| dir == parent))
+                });
+        self.modules = kept;
+        for mut module in removed {
+            module.shutdown();
+            tracing::info!("module {} has been removed", module.name);
+        }
+
+        for modules_path in modules_paths {
+            let path_and_names = match list_modules(modules_path) {
+                Ok(path_and_names) => path_and_names,
+                Err(err) => {
+                    tracing::error!(
+                        "couldn't list modules in {}: {err:#}",
+                        modules_path.to_string_lossy()
                     );
-                    let module_state = ModuleState {
-                        apis: Apis::new(name.clone(), db.clone())?,
-                    };
-
-                    let mut store = wasmtime::Store::new(&engine, module_state);
-                    let mut linker = wasmtime::component::Linker::new(&engine);
-
-                    apis::Apis::link(&mut linker)?;
-
-                    tracing::debug!("compiling");
-                    let component =
-                        wasmtime::component::Component::from_file(&engine, &module_path)?;
-
-                    tracing::debug!("instantiating");
-                    let instance =
-                        module::TrinityModule::instantiate(&mut store, &component, &linker)?;
-
-                    // Convert the module config to Vec of tuples to satisfy wasm interface types.
-                    let init_config: Option<Vec<(String, String)>> = modules_config
-                        .get(&name)
-                        .map(|mc| Vec::from_iter(mc.clone()));
-
-                    tracing::debug!("calling module's init() function");
-                    instance
-                        .trinity_module_messaging()
-                        .call_init(&mut store, init_config.as_deref())?;
-
-                    tracing::debug!("great success!");
-                    Ok(Module {
-                        name,
-                        instance,
-                        store,
-                    })
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+                    failures.push(LoadFailure {
+                        name: modules_path.to_string_lossy().into_owned(),
+                        error: err.context("couldn't list the modules"),
+                    });
+                    continue;
+                }
+            };
 
-            compiled_modules.extend(batch);
+            // New modules are loaded, and unchanged ones are skipped, unless reconfigured.
+            for (path, name) in path_and_names {
+                let force = reconfigured.contains(&name);
+                if let Some(failure) = self.reload_path(engine, host, path, modules_config, force) {
+                    failures.push(failure);
+                }
+            }
         }
 
-        Ok(Self {
-            modules: compiled_modules,
-        })
+        failures
+    }
+
+    /// Reload the module at the given path, if its content changed or `force` is set.
+    fn reload_path(
+        &mut self,
+        engine: &wasmtime::Engine,
+        host: &HostContext,
+        path: PathBuf,
+        modules_config: &HashMap<String, serde_json::Value>,
+        force: bool,
+    ) -> Option<LoadFailure> {
+        let position = self.modules.iter().position(|m| m.path == path);
+        let name = module_name(&path);
+
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                if let Some(position) = position {
+                    let mut module = self.modules.remove(position);
+                    module.shutdown();
+                    tracing::info!("module {} has been removed", module.name);
+                }
+                return None;
+            }
+            Err(err) => {
+                let failure = LoadFailure {
+                    name,
+                    error: err.into(),
+                };
+                tracing::error!("couldn't reload module {failure}");
+                return Some(failure);
+            }
+        };
+
+        let unchanged =
+            position.is_some_and(|position| self.modules[position].hash == content_hash(&bytes));
+        if unchanged && !force {
+            tracing::trace!("module at {} didn't change", path.to_string_lossy());
+            return None;
+        }
+
+        // Check the new version can be loaded before touching the previous one, which keeps
+        // running otherwise.
+        let mut module =
+            match compile_module(engine, host, path, name.clone(), &bytes, modules_config) {
+                Ok(module) => module,
+                Err(error) => {
+                    let failure = LoadFailure { name, error };
+                    if position.is_some() {
+                        tracing::error!(
+                            "couldn't reload module {failure}; keeping previous version"
+                        );
+                    } else {
+                        tracing::error!("couldn't load module {failure}");
+                    }
+                    return Some(failure);
+                }
+            };
+
+        // Let the previous version flush its state, before the new version reads it.
+        if let Some(position) = position {
+            self.modules[position].shutdown();
+        }
+
+        let version = crate::migrations::module_version(&host.db, &name);
+        if let Err(error) = init_module(&mut module, host) {
+            let failure = LoadFailure { name, error };
+            let Some(position) = position else {
+                tracing::error!("couldn't load module {failure}");
+                return Some(failure);
+            };
+
+            // The previous version can't run on data migrated for the new version.
+            let migrated = match (
+                version,
+                crate::migrations::module_version(&host.db, &module.name),
+            ) {
+                (Ok(before), Ok(after)) => before != after,
+                _ => true,
+            };
+            if migrated {
+                tracing::error!(
+                    "couldn't reload module {failure}; unloading it, since its data has been \
+                    migrated for the new version"
+                );
+                self.modules.remove(position);
+                return Some(failure);
+            }
+
+            // The previous version has been shut down already: bring it back up.
+            if let Err(err) = self.modules[position].init() {
+                tracing::error!(
+                    "couldn't reload module {failure}, nor restart the previous version: {err:#}"
+                );
+                self.modules.remove(position);
+            } else {
+                tracing::error!("couldn't reload module {failure}; keeping previous version");
+            }
+            return Some(failure);
+        }
+
+        if let Some(position) = position {
+            tracing::info!("module {} has been updated", module.name);
+            self.modules[position] = module;
+        } else {
+            tracing::info!("module {} has been added", module.name);
+            self.modules.push(module);
+        }
+        None
+    }
+
+    /// Logs the data migrations that would run for the modules in the given paths, without
+    /// running them nor initializing the modules.
+    pub fn dry_run_migrations(
+        engine: &wasmtime::Engine,
+        host: &HostContext,
+        modules_paths: &[PathBuf],
+    ) -> anyhow::Result<()> {
+        for modules_path in modules_paths {
+            for (module_path, name) in list_modules(modules_path)? {
+                let bytes = std::fs::read(&module_path)?;
+                let (instance, mut store, _) =
+                    instantiate(engine, host, &module_path, &name, &bytes, None)?;
+                run_migrations(&host.db, &name, &instance, &mut store, true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Let all the modules know they're about to be unloaded.
+    pub fn shutdown(&mut self) {
+        for module in &mut self.modules {
+            module.shutdown();
+        }
     }
 
     pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut Module> {
         self.modules.iter_mut()
     }
 }
+
+/// Create the wasm engine used to compile all the modules.
+pub(crate) fn create_engine() -> anyhow::Result<wasmtime::Engine> {
+    let mut config = wasmtime::Config::new();
+    config.wasm_component_model(true);
+    config.cache_config_load_default()?;
+    wasmtime::Engine::new(&config)
+}
+
+/// Collect all the modules paths and names in the given directory.
+fn list_modules(modules_path: &Path) -> anyhow::Result<Vec<(PathBuf, String)>> {
+    let mut path_and_names = vec![];
+    for module_path in std::fs::read_dir(modules_path)? {
+        let module_path = module_path?.path();
+
+        if module_path.extension().is_none_or(|ext| ext != "wasm") {
+            continue;
+        }
+
+        let module_path = normalize_path(&module_path);
+        let name = module_name(&module_path);
+        path_and_names.push((module_path, name));
+    }
+    Ok(path_and_names)
+}
+
+/// The name of a module is the file stem of its path.
+fn module_name(module_path: &Path) -> String {
+    module_path
+        .file_stem()
+        .map(|s| s.to_string_lossy())
+        .unwrap_or_else(|| module_path.to_string_lossy())
+        .to_string()
+}
+
+/// Canonicalizes the directory part of a path, so paths reported by the file watcher can be
+/// compared to the paths of loaded modules, even when the file doesn't exist anymore.
+fn normalize_path(path: &Path) -> PathBuf {
+    match (
+        path.parent().and_then(|parent| parent.canonicalize().ok()),
+        path.file_name(),
+    ) {
+        (Some(parent), Some(file_name)) => parent.join(file_name),
+        _ => path.to_owned(),
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Compile, instantiate, migrate and initialize a single module.
+fn load_module(
+    engine: &wasmtime::Engine,
+    host: &HostContext,
+    module_path: PathBuf,
+    name: String,
+    bytes: &[u8],
+    modules_config: &HashMap<String, serde_json::Value>,
+) -> anyhow::Result<Module> {
+    let mut module = compile_module(engine, host, module_path, name, bytes, modules_config)?;
+    init_module(&mut module, host)?;
+    Ok(module)
+}
+
+/// Compile and instantiate a single module, and check its manifest and configuration, without
+/// running anything that could change its stored data.
+fn compile_module(
+    engine: &wasmtime::Engine,
+    host: &HostContext,
+    module_path: PathBuf,
+    name: String,
+    bytes: &[u8],
+    modules_config: &HashMap<String, serde_json::Value>,
+) -> anyhow::Result<Module> {
+    let span = tracing::debug_span!("compiling module", name = %name, );
+    let _scope = span.enter();
+
+    // The config is passed as JSON, since WIT has no type for arbitrary trees of values.
+    let config = modules_config
+        .get(&name)
+        .map(serde_json::to_string)
+        .transpose()?;
+
+    let (instance, store, manifest) =
+        instantiate(engine, host, &module_path, &name, bytes, config.as_deref())?;
+
+    check_config(&manifest, modules_config.get(&name))?;
+
+    let triggers = manifest
+        .triggers
+        .iter()
+        .map(CompiledTrigger::new)
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Module {
+        name,
+        path: module_path,
+        hash: content_hash(bytes),
+        manifest,
+        triggers,
+        config,
+        instance,
+        store,
+    })
+}
+
+/// Run the pending data migrations of a compiled module, then initialize it.
+fn init_module(module: &mut Module, host: &HostContext) -> anyhow::Result<()> {
+    let span = tracing::debug_span!("initializing module", name = %module.name, );
+    let _scope = span.enter();
+
+    tracing::debug!("running module's data migrations");
+    run_migrations(
+        &host.db,
+        &module.name,
+        &module.instance,
+        &mut module.store,
+        false,
+    )?;
+
+    tracing::debug!("calling module's init() function");
+    module.init()?;
+
+    tracing::debug!("great success!");
+    Ok(())
+}
+
+/// Compile and instantiate a single module, without initializing it.
+///
+/// This also retrieves the module's manifest for the given config, and checks that the module
+/// only imports the host interfaces it declared in there.
+fn instantiate(
+    engine: &wasmtime::Engine,
+    host: &HostContext,
+    module_path: &Path,
+    name: &str,
+    bytes: &[u8],
+    config: Option<&str>,
+) -> anyhow::Result<(TrinityModule, Store<ModuleState>, ModuleManifest)> {
+    tracing::debug!(
+        path = module_path.to_str().unwrap_or("<invalid path>"),
+        "initializing: creating APIs"
+    );
+    let module_state = ModuleState {
+        apis: Apis::new(name.to_owned(), host)?,
+    };
+
+    let mut store = wasmtime::Store::new(engine, module_state);
+    let mut linker = wasmtime::component::Linker::new(engine);
+
+    apis::Apis::link(&mut linker)?;
+
+    tracing::debug!("compiling");
+    let component = wasmtime::component::Component::new(engine, bytes)?;
+
+    tracing::debug!("instantiating");
+    let instance = module::TrinityModule::instantiate(&mut store, &component, &linker)?;
+
+    let manifest = instance
+        .trinity_module_messaging()
+        .call_manifest(&mut store, config)?;
+    check_capabilities(engine, &component, &manifest)?;
+
+    Ok((instance, store, manifest))
+}
+
+/// Maps a host interface name, as imported by a component, to the corresponding capability.
+fn capability_for_import(import: &str) -> Option<Capability> {
+    Some(match import {
+        "trinity:api/kv" => Capability::Kv,
+        "trinity:api/log" => Capability::Log,
+        "trinity:api/sync-request" => Capability::SyncRequest,
+        "trinity:api/sys" => Capability::Sys,
+        "trinity:api/history" => Capability::History,
+        "trinity:api/room-info" => Capability::RoomInfo,
+        "trinity:api/room-state" => Capability::RoomState,
+        "trinity:api/typing" => Capability::Typing,
+        _ => return None,
+    })
+}
+
+/// Checks that a component only imports host interfaces declared in its manifest.
+fn check_capabilities(
+    engine: &wasmtime::Engine,
+    component: &wasmtime::component::Component,
+    manifest: &ModuleManifest,
+) -> anyhow::Result<()> {
+    for (import, _) in component.component_type().imports(engine) {
+        let Some(capability) = capability_for_import(import) else {
+            anyhow::bail!("module imports unknown interface {import}");
+        };
+        anyhow::ensure!(
+            manifest.capabilities.contains(&capability),
+            "module imports {import}, but doesn't declare it in its manifest's capabilities"
+        );
+    }
+    Ok(())
+}
+
+/// Checks a module's configuration against the configuration schema declared in its manifest.
+fn check_config(
+    manifest: &ModuleManifest,
+    config: Option<&serde_json::Value>,
+) -> anyhow::Result<()> {
+    let config = match config {
+        Some(serde_json::Value::Object(config)) => Some(config),
+        Some(_) => anyhow::bail!("the module's config must be a table"),
+        None => None,
+    };
+
+    for key in &manifest.config_schema {
+        anyhow::ensure!(
+            !key.required || config.is_some_and(|config| config.contains_key(&key.name)),
+            "missing required config key {} ({})",
+            key.name,
+            key.description
+        );
+    }
+
+    for name in config.into_iter().flat_map(|config| config.keys()) {
+        if !manifest.config_schema.iter().any(|key| key.name == *name) {
+            tracing::warn!("unknown config key {name}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Run the pending data migrations declared by a module.
+fn run_migrations(
+    db: &ShareableDatabase,
+    name: &str,
+    instance: &TrinityModule,
+    store: &mut Store<ModuleState>,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let messaging = instance.trinity_module_messaging();
+    let declared = messaging.call_migrations(&mut *store)?;
+    crate::migrations::run_module(db, name, &declared, dry_run, |migration| {
+        messaging
+            .call_migrate(&mut *store, migration)?
+            .map_err(|err| anyhow::anyhow!(err))
+    })
+}
@@ -0,0 +1,141 @@
+use matrix_sdk::{
+    Client,
+    room::MessagesOptions,
+    ruma::{
+        UInt,
+        events::{
+            AnySyncMessageLikeEvent, AnySyncTimelineEvent, SyncMessageLikeEvent,
+            room::message::{MessageType, Relation},
+        },
+    },
+};
+
+use crate::wasm::apis::history::trinity::api::history;
+use crate::wasm::{ModuleState, Permission};
+
+use super::RoomContext;
+
+wasmtime::component::bindgen!({
+    path: "./wit/history.wit",
+    world: "history-world"
+});
+
+use history::*;
+
+/// Number of events requested to the homeserver, per page.
+const PAGE_SIZE: u32 = 100;
+
+/// Maximum number of pages fetched for a single request, so a request for a quiet thread in a
+/// busy room doesn't go through the whole room history.
+const MAX_PAGES: usize = 10;
+
+pub(super) struct HistoryApi {
+    client: Option<Client>,
+    allowed: bool,
+    pub room: Option<RoomContext>,
+}
+
+impl HistoryApi {
+    pub fn new(client: Option<Client>, permissions: &[Permission]) -> Self {
+        Self {
+            client,
+            allowed: permissions.contains(&Permission::ReadHistory),
+            room: None,
+        }
+    }
+
+    pub fn link(linker: &mut wasmtime::component::Linker<ModuleState>) -> anyhow::Result<()> {
+        history::add_to_linker(linker, move |s| &mut s.apis.history)
+    }
+
+    /// Paginates backwards in the current room (or thread), until `stop` returns true for an
+    /// event, or there's no more history.
+    fn read(
+        &self,
+        mut stop: impl FnMut(&[Event], &Event) -> bool,
+    ) -> Result<Vec<Event>, HistoryError> {
+        if !self.allowed {
+            return Err(HistoryError::PermissionDenied);
+        }
+        let (Some(client), Some(context)) = (&self.client, &self.room) else {
+            return Err(HistoryError::NoRoom);
+        };
+        let room = client.get_room(&context.room).ok_or(HistoryError::NoRoom)?;
+
+        let mut events = Vec::new();
+        let mut from = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut options = MessagesOptions::backward();
+            options.limit = UInt::from(PAGE_SIZE);
+            options.from = from.take();
+
+            let messages = futures::executor::block_on(room.messages(options))
+                .map_err(|err| HistoryError::Internal(err.to_string()))?;
+
+            for timeline_event in messages.chunk {
+                let Ok(AnySyncTimelineEvent::MessageLike(AnySyncMessageLikeEvent::RoomMessage(
+                    SyncMessageLikeEvent::Original(ev),
+                ))) = timeline_event.raw().deserialize()
+                else {
+                    continue;
+                };
+
+                let in_thread = matches!(
+                    &ev.content.relates_to,
+                    Some(Relation::Thread(thread)) if Some(&thread.event_id) == context.thread_root.as_ref()
+                );
+                let is_thread_root = Some(&ev.event_id) == context.thread_root.as_ref();
+                if context.thread_root.is_some() && !in_thread && !is_thread_root {
+                    continue;
+                }
+
+                // Edits are skipped; the original event is kept.
+                if matches!(ev.content.relates_to, Some(Relation::Replacement(_))) {
+                    continue;
+                }
+
+                let body = match &ev.content.msgtype {
+                    MessageType::Text(text) => text.body.clone(),
+                    MessageType::Notice(notice) => notice.body.clone(),
+                    MessageType::Emote(emote) => emote.body.clone(),
+                    _ => continue,
+                };
+
+                let event = Event {
+                    event_id: ev.event_id.to_string(),
+                    sender: ev.sender.to_string(),
+                    body,
+                    timestamp: ev.origin_server_ts.0.into(),
+                };
+
+                if stop(&events, &event) {
+                    events.reverse();
+                    return Ok(events);
+                }
+                events.push(event);
+            }
+
+            if messages.end.is_none() {
+                break;
+            }
+            from = messages.end;
+        }
+
+        events.reverse();
+        Ok(events)
+    }
+}
+
+impl history::Host for HistoryApi {
+    fn last(&mut self, limit: u32) -> Result<Vec<Event>, HistoryError> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        self.read(|events, _| events.len() >= limit as usize)
+    }
+
+    fn since(&mut self, timestamp: u64) -> Result<Vec<Event>, HistoryError> {
+        self.read(|_, event| event.timestamp < timestamp)
+    }
+}
@@ -58,6 +58,20 @@ impl KeyValueStoreApi {
         Ok(table.get(&key)?.map(|val| val.to_vec()))
     }
 
+    fn keys_impl(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        let table_def = TableDefinition::<[u8], [u8]>::new(&self.module_name);
+        let txn = self.db.begin_read()?;
+        let table = match txn.open_table(table_def) {
+            Ok(table) => table,
+            Err(redb::Error::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(err) => Err(err)?,
+        };
+        Ok(table
+            .range::<std::ops::RangeFull, &[u8]>(..)?
+            .map(|(key, _)| key.to_vec())
+            .collect())
+    }
+
     fn remove_impl(&mut self, key: Vec<u8>) -> anyhow::Result<()> {
         let table_def = TableDefinition::<[u8], [u8]>::new(&self.module_name);
         let txn = self.db.begin_write()?;
@@ -85,4 +99,9 @@ impl kv::Host for KeyValueStoreApi {
         self.remove_impl(key)
             .map_err(|err: anyhow::Error| kv::KvError::Internal(err.to_string()))
     }
+
+    fn keys(&mut self) -> Result<Vec<Vec<u8>>, kv::KvError> {
+        self.keys_impl()
+            .map_err(|err: anyhow::Error| kv::KvError::Internal(err.to_string()))
+    }
 }
@@ -1,31 +1,58 @@
+mod history;
 mod kv_store;
 mod log;
+mod room_info;
+mod room_state;
 mod sync_request;
 mod sys;
+mod typing;
 
-use crate::ShareableDatabase;
+use matrix_sdk::ruma::{OwnedEventId, OwnedRoomId};
 
+use crate::wasm::HostContext;
+
+use self::history::HistoryApi;
 use self::kv_store::KeyValueStoreApi;
 use self::log::LogApi;
+use self::room_info::RoomInfoApi;
+use self::room_state::RoomStateApi;
 use self::sync_request::SyncRequestApi;
 use self::sys::SysApi;
+use self::typing::TypingApi;
 
 use super::ModuleState;
 
+/// The room in which a module is handling a message.
+#[derive(Clone)]
+pub(crate) struct RoomContext {
+    pub room: OwnedRoomId,
+    /// Root of the thread the message belongs to, if any.
+    pub thread_root: Option<OwnedEventId>,
+}
+
 pub(crate) struct Apis {
     sys: SysApi,
     log: LogApi,
     sync_request: SyncRequestApi,
     kv_store: KeyValueStoreApi,
+    history: HistoryApi,
+    room_info: RoomInfoApi,
+    room_state: RoomStateApi,
+    typing: TypingApi,
 }
 
 impl Apis {
-    pub fn new(module_name: String, db: ShareableDatabase) -> anyhow::Result<Self> {
+    pub fn new(module_name: String, host: &HostContext) -> anyhow::Result<Self> {
+        let permissions = host.permissions(&module_name);
         Ok(Self {
             sys: SysApi {},
             log: LogApi::new(&module_name),
             sync_request: SyncRequestApi::default(),
-            kv_store: KeyValueStoreApi::new(db, &module_name)?,
+            kv_store: KeyValueStoreApi::new(host.db.clone(), &module_name)?,
+            history: HistoryApi::new(host.client.clone(), permissions),
+            room_info: RoomInfoApi::new(host.client.clone()),
+            room_state: RoomStateApi::new(host.client.clone(), permissions),
+            typing: TypingApi::new(&module_name, host.client.clone()),
         })
     }
 
@@ -34,6 +61,18 @@ impl Apis {
         log::LogApi::link(linker)?;
         sync_request::SyncRequestApi::link(linker)?;
         kv_store::KeyValueStoreApi::link(linker)?;
+        history::HistoryApi::link(linker)?;
+        room_info::RoomInfoApi::link(linker)?;
+        room_state::RoomStateApi::link(linker)?;
+        typing::TypingApi::link(linker)?;
         Ok(())
     }
+
+    /// Sets the room in which the module is handling a message, for the APIs that act on it.
+    pub fn set_room(&mut self, room: Option<RoomContext>) {
+        self.history.room = room.clone();
+        self.room_info.room = room.clone();
+        self.room_state.room = room.clone();
+        self.typing.room = room;
+    }
 }
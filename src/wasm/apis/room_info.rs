@@ -0,0 +1,98 @@
+use matrix_sdk::{Client, RoomMemberships};
+
+use crate::wasm::ModuleState;
+use crate::wasm::apis::room_info::trinity::api::room_info;
+
+use super::RoomContext;
+
+wasmtime::component::bindgen!({
+    path: "./wit/room-info.wit",
+    world: "room-info-world"
+});
+
+use room_info::*;
+
+pub(super) struct RoomInfoApi {
+    client: Option<Client>,
+    pub room: Option<RoomContext>,
+}
+
+impl RoomInfoApi {
+    pub fn new(client: Option<Client>) -> Self {
+        Self { client, room: None }
+    }
+
+    pub fn link(linker: &mut wasmtime::component::Linker<ModuleState>) -> anyhow::Result<()> {
+        room_info::add_to_linker(linker, move |s| &mut s.apis.room_info)
+    }
+
+    fn current_room(&self) -> Result<matrix_sdk::room::Room, RoomInfoError> {
+        let (Some(client), Some(context)) = (&self.client, &self.room) else {
+            return Err(RoomInfoError::NoRoom);
+        };
+        client.get_room(&context.room).ok_or(RoomInfoError::NoRoom)
+    }
+}
+
+fn internal(err: impl ToString) -> RoomInfoError {
+    RoomInfoError::Internal(err.to_string())
+}
+
+impl room_info::Host for RoomInfoApi {
+    fn info(&mut self) -> Result<Room, RoomInfoError> {
+        let room = self.current_room()?;
+
+        let (encrypted, is_direct) = futures::executor::block_on(async {
+            let encryption = room.latest_encryption_state().await?;
+            let is_direct = room.is_direct().await?;
+            anyhow::Ok((encryption.is_encrypted(), is_direct))
+        })
+        .map_err(internal)?;
+
+        Ok(Room {
+            room_id: room.room_id().to_string(),
+            name: room.name(),
+            topic: room.topic(),
+            canonical_alias: room.canonical_alias().map(|alias| alias.to_string()),
+            encrypted,
+            is_direct,
+        })
+    }
+
+    fn members(&mut self) -> Result<Vec<Member>, RoomInfoError> {
+        let room = self.current_room()?;
+
+        let members =
+            futures::executor::block_on(room.members(RoomMemberships::JOIN)).map_err(internal)?;
+
+        Ok(members
+            .into_iter()
+            .map(|member| Member {
+                user_id: member.user_id().to_string(),
+                display_name: member.display_name().map(ToOwned::to_owned),
+                power_level: member.power_level(),
+            })
+            .collect())
+    }
+
+    fn power_levels(&mut self) -> Result<RoomPowerLevels, RoomInfoError> {
+        let room = self.current_room()?;
+
+        let power_levels = futures::executor::block_on(room.power_levels()).map_err(internal)?;
+
+        Ok(RoomPowerLevels {
+            users_default: power_levels.users_default.into(),
+            users: power_levels
+                .users
+                .into_iter()
+                .map(|(user_id, level)| (user_id.to_string(), level.into()))
+                .collect(),
+            events_default: power_levels.events_default.into(),
+            state_default: power_levels.state_default.into(),
+            ban: power_levels.ban.into(),
+            kick: power_levels.kick.into(),
+            redact: power_levels.redact.into(),
+            invite: power_levels.invite.into(),
+        })
+    }
+}
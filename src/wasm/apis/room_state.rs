@@ -0,0 +1,123 @@
+use std::future::Future;
+
+use matrix_sdk::{
+    Client,
+    room::Room,
+    ruma::{EventId, OwnedEventId, events::room::pinned_events::RoomPinnedEventsEventContent},
+};
+
+use crate::wasm::apis::room_state::trinity::api::room_state;
+use crate::wasm::{ModuleState, Permission};
+
+use super::RoomContext;
+
+wasmtime::component::bindgen!({
+    path: "./wit/room-state.wit",
+    world: "room-state-world"
+});
+
+use room_state::*;
+
+pub(super) struct RoomStateApi {
+    client: Option<Client>,
+    allowed: bool,
+    pub room: Option<RoomContext>,
+}
+
+impl RoomStateApi {
+    pub fn new(client: Option<Client>, permissions: &[Permission]) -> Self {
+        Self {
+            client,
+            allowed: permissions.contains(&Permission::WriteState),
+            room: None,
+        }
+    }
+
+    pub fn link(linker: &mut wasmtime::component::Linker<ModuleState>) -> anyhow::Result<()> {
+        room_state::add_to_linker(linker, move |s| &mut s.apis.room_state)
+    }
+
+    /// Runs the given change against the current room, if the module is allowed to.
+    fn change<F, Fut, T>(&self, f: F) -> Result<(), RoomStateError>
+    where
+        F: FnOnce(matrix_sdk::room::Room) -> Fut,
+        Fut: Future<Output = matrix_sdk::Result<T>>,
+    {
+        if !self.allowed {
+            return Err(RoomStateError::PermissionDenied);
+        }
+        let (Some(client), Some(context)) = (&self.client, &self.room) else {
+            return Err(RoomStateError::NoRoom);
+        };
+        let room = client
+            .get_room(&context.room)
+            .ok_or(RoomStateError::NoRoom)?;
+
+        futures::executor::block_on(f(room)).map_err(|err| {
+            if err
+                .as_client_api_error()
+                .is_some_and(|err| err.status_code.as_u16() == 403)
+            {
+                RoomStateError::Forbidden(err.to_string())
+            } else {
+                RoomStateError::Internal(err.to_string())
+            }
+        })?;
+
+        Ok(())
+    }
+}
+
+impl room_state::Host for RoomStateApi {
+    fn set_topic(&mut self, topic: String) -> Result<(), RoomStateError> {
+        self.change(|room| async move { room.set_room_topic(&topic).await })
+    }
+
+    fn set_name(&mut self, name: String) -> Result<(), RoomStateError> {
+        self.change(|room| async move { room.set_name(name).await })
+    }
+
+    fn pin(&mut self, event_id: String) -> Result<(), RoomStateError> {
+        let event_id =
+            EventId::parse(event_id).map_err(|err| RoomStateError::Invalid(err.to_string()))?;
+        self.change(|room| {
+            update_pins(room, move |pinned| {
+                if !pinned.contains(&event_id) {
+                    pinned.push(event_id);
+                }
+            })
+        })
+    }
+
+    fn unpin(&mut self, event_id: String) -> Result<(), RoomStateError> {
+        let event_id =
+            EventId::parse(event_id).map_err(|err| RoomStateError::Invalid(err.to_string()))?;
+        self.change(|room| update_pins(room, move |pinned| pinned.retain(|id| *id != event_id)))
+    }
+
+    fn send_state_event(
+        &mut self,
+        event_type: String,
+        state_key: String,
+        content: String,
+    ) -> Result<(), RoomStateError> {
+        let content = serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|err| RoomStateError::Invalid(err.to_string()))?;
+        self.change(|room| async move {
+            room.send_state_event_raw(&event_type, &state_key, content)
+                .await
+        })
+    }
+}
+
+/// Changes the list of pinned events of the room.
+async fn update_pins(
+    room: Room,
+    update: impl FnOnce(&mut Vec<OwnedEventId>),
+) -> matrix_sdk::Result<()> {
+    let mut pinned = room.load_pinned_events().await?.unwrap_or_default();
+    update(&mut pinned);
+    room.send_state_event(RoomPinnedEventsEventContent::new(pinned))
+        .await?;
+    Ok(())
+}
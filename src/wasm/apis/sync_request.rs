@@ -19,8 +19,8 @@ impl SyncRequestApi {
     }
 }
 
-impl sync_request::Host for SyncRequestApi {
-    fn run_request(&mut self, req: Request) -> Result<Response, RunRequestError> {
+impl SyncRequestApi {
+    fn execute(&self, req: Request) -> Result<reqwest::blocking::Response, RunRequestError> {
         let url = req.url;
         let mut builder = match req.verb {
             RequestVerb::Get => self.client.get(url),
@@ -38,18 +38,42 @@ impl sync_request::Host for SyncRequestApi {
             .build()
             .map_err(|err| RunRequestError::Builder(err.to_string()))?;
 
-        let resp = self
-            .client
+        self.client
             .execute(req)
-            .map_err(|err| RunRequestError::Execute(err.to_string()))?;
+            .map_err(|err| RunRequestError::Execute(err.to_string()))
+    }
+}
 
-        let status = match resp.status().as_u16() / 100 {
-            2 => ResponseStatus::Success,
-            _ => ResponseStatus::Error,
-        };
+fn response_status(resp: &reqwest::blocking::Response) -> ResponseStatus {
+    match resp.status().as_u16() / 100 {
+        2 => ResponseStatus::Success,
+        _ => ResponseStatus::Error,
+    }
+}
 
+impl sync_request::Host for SyncRequestApi {
+    fn run_request(&mut self, req: Request) -> Result<Response, RunRequestError> {
+        let resp = self.execute(req)?;
+        let status = response_status(&resp);
         let body = resp.text().ok();
 
         Ok(Response { status, body })
     }
+
+    fn run_binary_request(&mut self, req: Request) -> Result<BinaryResponse, RunRequestError> {
+        let resp = self.execute(req)?;
+        let status = response_status(&resp);
+        let content_type = resp
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(ToOwned::to_owned);
+        let body = resp.bytes().ok().map(|bytes| bytes.to_vec());
+
+        Ok(BinaryResponse {
+            status,
+            content_type,
+            body,
+        })
+    }
 }
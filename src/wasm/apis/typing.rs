@@ -0,0 +1,49 @@
+use matrix_sdk::Client;
+
+use crate::wasm::ModuleState;
+use crate::wasm::apis::typing::trinity::api::typing;
+
+use super::RoomContext;
+
+wasmtime::component::bindgen!({
+    path: "./wit/typing.wit",
+    world: "typing-world"
+});
+
+pub(super) struct TypingApi {
+    module_name: String,
+    client: Option<Client>,
+    pub room: Option<RoomContext>,
+}
+
+impl TypingApi {
+    pub fn new(module_name: &str, client: Option<Client>) -> Self {
+        Self {
+            module_name: module_name.to_owned(),
+            client,
+            room: None,
+        }
+    }
+
+    pub fn link(linker: &mut wasmtime::component::Linker<ModuleState>) -> anyhow::Result<()> {
+        typing::add_to_linker(linker, move |s| &mut s.apis.typing)
+    }
+}
+
+impl typing::Host for TypingApi {
+    fn set_typing(&mut self, typing: bool) {
+        let (Some(client), Some(context)) = (&self.client, &self.room) else {
+            tracing::warn!(
+                "{} - typing notice requested outside of a room",
+                self.module_name
+            );
+            return;
+        };
+        let Some(room) = client.get_room(&context.room) else {
+            return;
+        };
+        if let Err(err) = futures::executor::block_on(room.typing_notice(typing)) {
+            tracing::warn!("{} - couldn't send typing notice: {err}", self.module_name);
+        }
+    }
+}